@@ -0,0 +1,143 @@
+use crate::{
+    asset::{
+        hitbox::HitboxShape,
+        path::{PathLibrary, PathMode},
+        pattern::BulletPattern,
+    },
+    component::sprite::SpriteSize,
+};
+use bevy::{prelude::*, reflect::TypePath};
+use serde::Deserialize;
+
+/// Asset - Boss definitions (`*.bosses.ron`)
+#[derive(Asset, TypePath, Deserialize, Debug, Clone)]
+pub struct BossLibrary {
+    pub bosses: Vec<BossDef>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct BossDef {
+    pub name: String,
+    /// sprite name in Spritesheet/sheet.xml
+    pub sprite: String,
+    /// hitbox (unscaled sprite pixels)
+    pub size: (f32, f32),
+    #[serde(default)]
+    pub hitbox: HitboxShape,
+    /// drawn this much bigger than regular sprites
+    #[serde(default = "default_scale")]
+    pub scale: f32,
+    pub hp: u32,
+    pub score: u32,
+    /// phases by decreasing `threshold`, the first one starts at full health
+    pub phases: Vec<BossPhase>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct BossPhase {
+    /// the phase starts once the health fraction drops to this
+    pub threshold: f32,
+    /// units per second
+    pub speed: f32,
+    pub movement: BossMovement,
+    /// bullet patterns, fired by the boss emitter
+    #[serde(default)]
+    pub patterns: Vec<BulletPattern>,
+    #[serde(default)]
+    pub beams: Vec<BossBeamPattern>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub enum BossMovement {
+    /// hover at the top, swaying side to side by up to `sway` units
+    Hold { sway: f32 },
+    /// elliptical orbit around the top center
+    Orbit { radius: (f32, f32) },
+    /// follow the named path of the path library, it must loop or ping-pong
+    Path { path: String, mode: PathMode },
+}
+
+/// Beam of `segments` sweeping over `sweep` degrees below the boss,
+/// harmless for `warmup` seconds then deadly for `duration` seconds
+#[derive(Deserialize, Debug, Clone)]
+pub struct BossBeamPattern {
+    pub segments: u32,
+    pub sweep: f32,
+    pub warmup: f32,
+    pub duration: f32,
+    /// seconds between two beams
+    pub interval: f32,
+}
+
+impl BossBeamPattern {
+    /// Check the beam has segments and a positive duration and interval
+    pub fn validate(&self) -> Result<(), String> {
+        if self.segments == 0 {
+            return Err("beam has no segments".into());
+        }
+        if self.duration <= 0. || self.interval <= 0. {
+            return Err("beam duration and interval must be positive".into());
+        }
+        Ok(())
+    }
+}
+
+fn default_scale() -> f32 {
+    1.
+}
+
+impl BossDef {
+    pub fn sprite_size(&self) -> SpriteSize {
+        SpriteSize {
+            w: self.size.0,
+            h: self.size.1,
+        }
+    }
+}
+
+impl BossLibrary {
+    pub fn get(&self, name: &str) -> Option<&BossDef> {
+        self.bosses.iter().find(|boss| boss.name == name)
+    }
+
+    /// Check every boss has a valid hitbox, ordered phases, valid patterns and beams and only
+    /// names known looping paths
+    pub fn validate(&self, paths: &PathLibrary) -> Result<(), String> {
+        for boss in self.bosses.iter() {
+            let name = &boss.name;
+            boss.hitbox
+                .validate()
+                .map_err(|err| format!("boss {name:?}: {err}"))?;
+            if boss.phases.is_empty() {
+                return Err(format!("boss {name:?} has no phases"));
+            }
+            if boss
+                .phases
+                .windows(2)
+                .any(|pair| pair[1].threshold >= pair[0].threshold)
+            {
+                return Err(format!("boss {name:?} phase thresholds must decrease"));
+            }
+            for phase in boss.phases.iter() {
+                for pattern in phase.patterns.iter() {
+                    pattern
+                        .validate()
+                        .map_err(|err| format!("boss {name:?}: {err}"))?;
+                }
+                for beam in phase.beams.iter() {
+                    beam.validate()
+                        .map_err(|err| format!("boss {name:?}: {err}"))?;
+                }
+                if let BossMovement::Path { path, mode } = &phase.movement {
+                    if paths.get(path).is_none() {
+                        return Err(format!("boss {name:?} uses unknown path {path:?}"));
+                    }
+                    if *mode == PathMode::DespawnAtEnd {
+                        return Err(format!("boss {name:?} path {path:?} must loop"));
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
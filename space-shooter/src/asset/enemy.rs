@@ -0,0 +1,91 @@
+use crate::{
+    asset::{hitbox::HitboxShape, pattern::BulletPattern},
+    component::sprite::SpriteSize,
+};
+use bevy::{prelude::*, reflect::TypePath};
+use serde::Deserialize;
+
+/// Asset - Enemy archetypes (`*.enemies.ron`)
+#[derive(Asset, TypePath, Deserialize, Debug, Clone)]
+pub struct EnemyArchetypes {
+    pub archetypes: Vec<EnemyArchetype>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct EnemyArchetype {
+    pub name: String,
+    /// sprite name in Spritesheet/sheet.xml
+    pub sprite: String,
+    /// hitbox (unscaled sprite pixels)
+    pub size: (f32, f32),
+    #[serde(default)]
+    pub hitbox: HitboxShape,
+    pub hp: u32,
+    /// formation speed, units per second
+    pub speed: f32,
+    /// bullet patterns, fired once the enemy is on screen
+    #[serde(default)]
+    pub fire: Vec<BulletPattern>,
+    pub score: u32,
+    /// relative chance to be picked when spawning
+    #[serde(default = "default_weight")]
+    pub weight: f32,
+}
+
+fn default_weight() -> f32 {
+    1.
+}
+
+impl EnemyArchetype {
+    pub fn sprite_size(&self) -> SpriteSize {
+        SpriteSize {
+            w: self.size.0,
+            h: self.size.1,
+        }
+    }
+}
+
+impl EnemyArchetypes {
+    pub fn get(&self, name: &str) -> Option<&EnemyArchetype> {
+        self.archetypes
+            .iter()
+            .find(|archetype| archetype.name == name)
+    }
+
+    /// Check the weight, speed, hitbox and bullet patterns of every archetype
+    pub fn validate(&self) -> Result<(), String> {
+        // waves without an enemy mix pick by archetype weight
+        if !self
+            .archetypes
+            .iter()
+            .any(|archetype| archetype.weight > 0.)
+        {
+            return Err("no archetype has a positive weight".into());
+        }
+        for archetype in self.archetypes.iter() {
+            if archetype.weight < 0. {
+                return Err(format!(
+                    "archetype {:?}: weight cannot be negative",
+                    archetype.name
+                ));
+            }
+            // formations trail their members by spacing over speed
+            if archetype.speed <= 0. {
+                return Err(format!(
+                    "archetype {:?}: speed must be positive",
+                    archetype.name
+                ));
+            }
+            archetype
+                .hitbox
+                .validate()
+                .map_err(|err| format!("archetype {:?}: {err}", archetype.name))?;
+            for pattern in archetype.fire.iter() {
+                pattern
+                    .validate()
+                    .map_err(|err| format!("archetype {:?}: {err}", archetype.name))?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,67 @@
+use crate::asset::path::PathMode;
+use serde::Deserialize;
+
+/// One kind of formation a wave can spawn
+#[derive(Deserialize, Debug, Clone)]
+pub struct FormationSpec {
+    pub kind: FormationKind,
+    pub members: u32,
+    /// distance between two members, units
+    pub spacing: f32,
+    /// relative chance to be picked within the wave
+    #[serde(default = "default_weight")]
+    pub weight: f32,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub enum FormationKind {
+    /// members trail each other into an elliptical orbit
+    Orbit,
+    /// V shape flying in from the top, swaying around its slot
+    V,
+    /// block of `columns` flying in from the top, then strafing side to side as a group
+    Grid { columns: u32 },
+    /// members cross the field along a sine wave
+    Sine { amplitude: f32, wavelength: f32 },
+    /// members line up at the top, then dive bomb one after the other
+    Dive,
+    /// members follow the named path of the path library
+    Path {
+        path: String,
+        #[serde(default)]
+        mode: PathMode,
+    },
+}
+
+impl Default for FormationSpec {
+    fn default() -> Self {
+        Self {
+            kind: FormationKind::Orbit,
+            members: 2,
+            spacing: 300.,
+            weight: 1.,
+        }
+    }
+}
+
+impl FormationSpec {
+    /// Check the formation has members, a spacing and, for sine waves, a wavelength
+    pub fn validate(&self) -> Result<(), String> {
+        if self.members == 0 {
+            return Err("formation has no members".into());
+        }
+        if self.spacing < 0. {
+            return Err("formation spacing cannot be negative".into());
+        }
+        if let FormationKind::Sine { wavelength, .. } = self.kind {
+            if wavelength == 0. {
+                return Err("sine formation needs a wavelength".into());
+            }
+        }
+        Ok(())
+    }
+}
+
+fn default_weight() -> f32 {
+    1.
+}
@@ -0,0 +1,63 @@
+use serde::Deserialize;
+
+/// Hitbox, in unscaled sprite pixels around the sprite center, y up
+#[derive(Deserialize, Debug, Clone, Default)]
+pub enum HitboxShape {
+    /// the whole sprite size rectangle
+    #[default]
+    Rect,
+    Circle {
+        radius: f32,
+        #[serde(default)]
+        center: (f32, f32),
+    },
+    /// every point within `radius` of the segment `from`-`to`
+    Capsule {
+        from: (f32, f32),
+        to: (f32, f32),
+        radius: f32,
+    },
+    /// convex polygon
+    Polygon { points: Vec<(f32, f32)> },
+    /// convex hull of the sprite pixels with an alpha above `threshold`,
+    /// computed from the sprite sheet while loading
+    Mask { threshold: f32 },
+}
+
+impl HitboxShape {
+    pub fn validate(&self) -> Result<(), String> {
+        match self {
+            Self::Circle { radius, .. } | Self::Capsule { radius, .. } if *radius <= 0. => {
+                Err("hitbox radius must be positive".into())
+            }
+            Self::Polygon { points } if points.len() < 3 => {
+                Err("hitbox polygon needs at least 3 points".into())
+            }
+            Self::Polygon { points } if !is_convex(points) => {
+                Err("hitbox polygon must be convex".into())
+            }
+            Self::Mask { threshold } if !(0. ..1.).contains(threshold) => {
+                Err("hitbox mask threshold must be within [0, 1)".into())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Every corner turns the same way
+fn is_convex(points: &[(f32, f32)]) -> bool {
+    let turns = (0..points.len()).map(|index| {
+        let (a, b, c) = (
+            points[index],
+            points[(index + 1) % points.len()],
+            points[(index + 2) % points.len()],
+        );
+        (b.0 - a.0) * (c.1 - b.1) - (b.1 - a.1) * (c.0 - b.0)
+    });
+    let (mut left, mut right) = (false, false);
+    for turn in turns {
+        left |= turn > 0.;
+        right |= turn < 0.;
+    }
+    !(left && right)
+}
@@ -0,0 +1,126 @@
+use crate::{asset::hitbox::HitboxShape, component::sprite::SpriteSize};
+use bevy::{prelude::*, reflect::TypePath};
+use serde::Deserialize;
+
+/// Asset - Meteor hazards (`*.meteors.ron`), spawned by the waves asking for them
+#[derive(Asset, TypePath, Deserialize, Debug, Clone)]
+pub struct MeteorField {
+    /// size chain, a shot meteor splits into pieces of the next size
+    pub sizes: Vec<MeteorSize>,
+    /// looks picked at random for new meteors, pieces keep the look of their meteor
+    pub kinds: Vec<MeteorKind>,
+    /// speed range of new meteors, units per second
+    pub speed: (f32, f32),
+    /// most degrees new meteors head away from straight down
+    pub drift: f32,
+    /// spin range, degrees per second either way
+    pub spin: (f32, f32),
+    /// speed pieces fly apart at, on top of the speed of their meteor
+    pub scatter: f32,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct MeteorSize {
+    pub name: String,
+    #[serde(default)]
+    pub hitbox: HitboxShape,
+    pub hp: u32,
+    pub score: u32,
+    /// hit points the player loses on contact
+    pub damage: u32,
+    /// pieces of the next size left when destroyed
+    #[serde(default)]
+    pub pieces: u32,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct MeteorKind {
+    pub name: String,
+    /// sprites by size, then (name in Spritesheet/sheet.xml, unscaled pixels)
+    pub sprites: Vec<Vec<(String, (f32, f32))>>,
+}
+
+impl MeteorKind {
+    /// Sprite `index` of `size`, wrapping around the sprites of that size
+    pub fn sprite(&self, size: usize, index: usize) -> (&str, SpriteSize) {
+        let sprites = &self.sprites[size];
+        let (sprite, (w, h)) = &sprites[index % sprites.len()];
+        (sprite, SpriteSize { w: *w, h: *h })
+    }
+}
+
+impl MeteorField {
+    /// Check the size chain and that every kind has sprites for every size
+    pub fn validate(&self) -> Result<(), String> {
+        if self.sizes.is_empty() || self.kinds.is_empty() {
+            return Err("no meteor sizes or kinds".into());
+        }
+        if self.speed.0 <= 0. || self.speed.0 > self.speed.1 || self.spin.0 > self.spin.1 {
+            return Err("speed and spin must be increasing ranges, the speed positive".into());
+        }
+        for size in self.sizes.iter() {
+            size.hitbox
+                .validate()
+                .map_err(|err| format!("size {:?}: {err}", size.name))?;
+            if size.hp == 0 {
+                return Err(format!("size {:?}: hp must be positive", size.name));
+            }
+        }
+        if self.sizes.last().is_some_and(|size| size.pieces > 0) {
+            return Err("the smallest size cannot split".into());
+        }
+        for kind in self.kinds.iter() {
+            if kind.sprites.len() != self.sizes.len()
+                || kind.sprites.iter().any(|sprites| sprites.is_empty())
+            {
+                return Err(format!("kind {:?} needs sprites for every size", kind.name));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field(sizes: &str, kinds: &str) -> MeteorField {
+        ron::from_str(&format!(
+            "(sizes: [{sizes}], kinds: [{kinds}], speed: (50., 90.), drift: 20., spin: (30., 90.), scatter: 40.)"
+        ))
+        .unwrap()
+    }
+
+    const SIZES: &str = r#"
+        (name: "big", hitbox: Circle(radius: 42.), hp: 6, score: 20, damage: 3, pieces: 2),
+        (name: "tiny", hp: 1, score: 80, damage: 1),
+    "#;
+    const KINDS: &str = r#"
+        (name: "brown", sprites: [[("big.png", (101., 84.))], [("tiny.png", (18., 18.))]]),
+    "#;
+
+    #[test]
+    fn valid_field() {
+        assert_eq!(field(SIZES, KINDS).validate(), Ok(()));
+    }
+
+    #[test]
+    fn smallest_size_cannot_split() {
+        let sizes = r#"(name: "big", hp: 6, score: 20, damage: 3, pieces: 2),"#;
+        let kinds = r#"(name: "brown", sprites: [[("big.png", (101., 84.))]]),"#;
+        assert!(field(sizes, kinds).validate().is_err());
+    }
+
+    #[test]
+    fn every_kind_needs_sprites_for_every_size() {
+        let kinds = r#"(name: "brown", sprites: [[("big.png", (101., 84.))], []]),"#;
+        assert!(field(SIZES, kinds).validate().is_err());
+    }
+
+    #[test]
+    fn ranges_must_increase() {
+        let mut meteors = field(SIZES, KINDS);
+        meteors.speed = (90., 50.);
+        assert!(meteors.validate().is_err());
+    }
+}
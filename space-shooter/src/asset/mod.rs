@@ -0,0 +1,12 @@
+pub mod boss;
+pub mod enemy;
+pub mod formation;
+pub mod hitbox;
+pub mod loader;
+pub mod meteor;
+pub mod path;
+pub mod pattern;
+pub mod pickup;
+pub mod sheet;
+pub mod wave;
+pub mod weapon;
@@ -0,0 +1,59 @@
+use bevy::{prelude::*, reflect::TypePath};
+use serde::Deserialize;
+
+/// Asset - Named movement paths (`*.paths.ron`)
+#[derive(Asset, TypePath, Deserialize, Debug, Clone)]
+pub struct PathLibrary {
+    pub paths: Vec<PathDef>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct PathDef {
+    pub name: String,
+    pub curve: PathCurve,
+    /// control points, in playfield half extents (-1..1)
+    pub points: Vec<(f32, f32)>,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PathCurve {
+    /// smooth curve through every point
+    CatmullRom,
+    /// chained cubic segments, `start, control, control, end, control, control, end...`
+    Bezier,
+}
+
+/// What a path follower does once it reaches the end of its path
+#[derive(Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PathMode {
+    /// start over from the beginning
+    Loop,
+    /// go back and forth
+    PingPong,
+    #[default]
+    DespawnAtEnd,
+}
+
+impl PathLibrary {
+    pub fn get(&self, name: &str) -> Option<&PathDef> {
+        self.paths.iter().find(|path| path.name == name)
+    }
+
+    /// Check every path has enough points for its curve
+    pub fn validate(&self) -> Result<(), String> {
+        for path in self.paths.iter() {
+            let count = path.points.len();
+            let valid = match path.curve {
+                PathCurve::CatmullRom => count >= 2,
+                PathCurve::Bezier => count >= 4 && (count - 1) % 3 == 0,
+            };
+            if !valid {
+                return Err(format!(
+                    "path {:?} has {count} points, not a valid {:?} curve",
+                    path.name, path.curve
+                ));
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,139 @@
+use serde::Deserialize;
+
+/// A volley of bullets, fired by a `BulletEmitter` every `interval` seconds
+#[derive(Deserialize, Debug, Clone)]
+pub struct BulletPattern {
+    pub kind: PatternKind,
+    /// bullet speed, units per second
+    pub speed: f32,
+    /// seconds between two volleys
+    pub interval: f32,
+    /// hit points each bullet takes off the player
+    #[serde(default = "default_damage")]
+    pub damage: u32,
+    #[serde(default)]
+    pub bullet: BulletKind,
+    /// x offsets from the emitter center, the volley is fired from each of them
+    #[serde(default = "default_muzzles")]
+    pub muzzles: Vec<f32>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub enum PatternKind {
+    /// `count` bullets fanned over `spread` degrees around straight down
+    Spread { count: u32, spread: f32 },
+    /// `arms` bullets evenly around a circle, turned `turn` degrees further every volley
+    Spiral { arms: u32, turn: f32 },
+    /// `count` bullets fanned over `spread` degrees, centered on the player
+    Aimed { count: u32, spread: f32 },
+    /// `shots` volleys of `pattern`, `gap` seconds apart, then the `interval` cooldown
+    Burst {
+        shots: u32,
+        gap: f32,
+        pattern: Box<PatternKind>,
+    },
+    /// `count` bullets fanned over `spread` degrees towards the player, turning towards it
+    /// by up to `turn_rate` degrees per second for `lifetime` seconds
+    Homing {
+        count: u32,
+        spread: f32,
+        turn_rate: f32,
+        lifetime: f32,
+    },
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BulletKind {
+    /// laser bolt, turned along its heading
+    #[default]
+    Laser,
+    /// round plasma ball
+    Orb,
+}
+
+fn default_damage() -> u32 {
+    1
+}
+
+fn default_muzzles() -> Vec<f32> {
+    vec![0.]
+}
+
+impl BulletPattern {
+    /// Check the pattern fires at least one bullet and never loops without waiting
+    pub fn validate(&self) -> Result<(), String> {
+        if self.interval <= 0. {
+            return Err("pattern interval must be positive".into());
+        }
+        if self.muzzles.is_empty() {
+            return Err("pattern has no muzzles".into());
+        }
+        self.kind.validate()
+    }
+}
+
+impl PatternKind {
+    fn validate(&self) -> Result<(), String> {
+        match self {
+            Self::Spread { count, .. } | Self::Aimed { count, .. } | Self::Homing { count, .. }
+                if *count == 0 =>
+            {
+                Err("pattern fires no bullets".into())
+            }
+            Self::Spiral { arms: 0, .. } => Err("spiral pattern has no arms".into()),
+            Self::Burst { shots, gap, .. } if *shots == 0 || *gap <= 0. => {
+                Err("burst needs shots and a positive gap".into())
+            }
+            Self::Burst { pattern, .. } if matches!(**pattern, Self::Burst { .. }) => {
+                Err("burst cannot fire bursts".into())
+            }
+            Self::Burst { pattern, .. } => pattern.validate(),
+            // a negative turn rate would turn the bullets away from the player
+            Self::Homing {
+                turn_rate,
+                lifetime,
+                ..
+            } if *turn_rate < 0. || *lifetime <= 0. => {
+                Err("homing needs a turn rate of 0 or more and a positive lifetime".into())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kind(ron: &str) -> PatternKind {
+        ron::from_str(ron).unwrap()
+    }
+
+    #[test]
+    fn valid_kinds() {
+        for ron in [
+            "Spread(count: 3, spread: 30.)",
+            "Burst(shots: 3, gap: 0.1, pattern: Aimed(count: 1, spread: 0.))",
+            "Homing(count: 1, spread: 0., turn_rate: 0., lifetime: 2.)",
+        ] {
+            assert_eq!(kind(ron).validate(), Ok(()), "{ron}");
+        }
+    }
+
+    #[test]
+    fn homing_needs_a_turn_rate_and_lifetime() {
+        for ron in [
+            "Homing(count: 1, spread: 0., turn_rate: -90., lifetime: 2.)",
+            "Homing(count: 1, spread: 0., turn_rate: 90., lifetime: 0.)",
+        ] {
+            assert!(kind(ron).validate().is_err(), "{ron}");
+        }
+    }
+
+    #[test]
+    fn burst_cannot_fire_bursts() {
+        let inner = "Burst(shots: 2, gap: 0.1, pattern: Spread(count: 3, spread: 30.))";
+        let ron = format!("Burst(shots: 3, gap: 0.1, pattern: {inner})");
+        assert!(kind(&ron).validate().is_err());
+    }
+}
@@ -0,0 +1,126 @@
+use crate::{
+    asset::weapon::{WeaponKind, WeaponLibrary},
+    component::sprite::SpriteSize,
+};
+use bevy::{prelude::*, reflect::TypePath};
+use serde::Deserialize;
+
+/// Asset - Power-ups dropped by destroyed enemies (`*.pickups.ron`)
+#[derive(Asset, TypePath, Deserialize, Debug, Clone)]
+pub struct LootTable {
+    /// chance a destroyed enemy drops anything, within [0, 1]
+    pub drop_chance: f32,
+    pub pickups: Vec<PickupDef>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct PickupDef {
+    pub name: String,
+    /// sprite name in Spritesheet/sheet.xml, also the HUD indicator
+    pub sprite: String,
+    /// sprite size (unscaled pixels)
+    pub size: (f32, f32),
+    pub effect: PickupEffect,
+    /// seconds the effect lasts, instant effects have none
+    #[serde(default)]
+    pub duration: f32,
+    /// what picking it up again does while the effect is active
+    #[serde(default)]
+    pub stacking: Stacking,
+    /// relative chance to be picked when dropping
+    #[serde(default = "default_weight")]
+    pub weight: f32,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PickupEffect {
+    /// one weapon level up per stack
+    Weapon,
+    /// instant, the ship shield absorbs `hits` more hits, up to `PLAYER_SHIELD_HITS`
+    Shield { hits: u32 },
+    /// kill points times `multiplier` per stack
+    ScoreMultiplier { multiplier: u32 },
+    /// instant, one more life up to `PLAYER_LIVES_MAX`
+    ExtraLife,
+    /// instant, `points` added to the score
+    Points { points: u32 },
+    /// instant, switch to `weapon` and keep it to cycle through
+    Arm { weapon: WeaponKind },
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Stacking {
+    /// the duration starts over
+    #[default]
+    Refresh,
+    /// the duration is added to the time left
+    Extend,
+    /// one more stack up to `max`, and the duration starts over
+    Stack { max: u32 },
+    /// nothing happens
+    Ignore,
+}
+
+fn default_weight() -> f32 {
+    1.
+}
+
+impl PickupEffect {
+    /// Applied once when picked up, never active
+    pub fn instant(&self) -> bool {
+        matches!(
+            self,
+            Self::Shield { .. } | Self::ExtraLife | Self::Points { .. } | Self::Arm { .. }
+        )
+    }
+}
+
+impl PickupDef {
+    pub fn sprite_size(&self) -> SpriteSize {
+        SpriteSize {
+            w: self.size.0,
+            h: self.size.1,
+        }
+    }
+
+    fn validate(&self, weapons: &WeaponLibrary) -> Result<(), String> {
+        if let PickupEffect::Arm { weapon } = self.effect {
+            if weapons.get(weapon).is_none() {
+                return Err(format!("unknown weapon {weapon:?}"));
+            }
+        }
+        if self.effect.instant() {
+            if self.duration != 0. {
+                return Err("instant effects take no duration".into());
+            }
+        } else if self.duration <= 0. {
+            return Err("duration must be positive".into());
+        }
+        match (self.effect, self.stacking) {
+            (_, Stacking::Stack { max: 0 }) => Err("stack max must be positive".into()),
+            (PickupEffect::ScoreMultiplier { multiplier: 0 }, _) => {
+                Err("score multiplier must be positive".into())
+            }
+            (PickupEffect::Shield { hits: 0 }, _) => Err("shield hits must be positive".into()),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl LootTable {
+    /// Check the drop chance and every pickup, weapons must be in `weapons`
+    pub fn validate(&self, weapons: &WeaponLibrary) -> Result<(), String> {
+        if !(0. ..=1.).contains(&self.drop_chance) {
+            return Err("drop chance must be within [0, 1]".into());
+        }
+        if self.drop_chance > 0. && !self.pickups.iter().any(|pickup| pickup.weight > 0.) {
+            return Err("nothing to drop".into());
+        }
+        for pickup in self.pickups.iter() {
+            pickup
+                .validate(weapons)
+                .map_err(|err| format!("pickup {:?}: {err}", pickup.name))?;
+        }
+        Ok(())
+    }
+}
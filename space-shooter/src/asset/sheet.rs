@@ -0,0 +1,230 @@
+use bevy::{
+    asset::{io::Reader, AssetLoader, AsyncReadExt, LoadContext, LoadDirectError},
+    prelude::*,
+    reflect::TypePath,
+    utils::{BoxedFuture, HashMap},
+};
+use std::fmt;
+use xml::{
+    attribute::OwnedAttribute,
+    reader::{EventReader, XmlEvent},
+};
+
+/// Asset - Sprite sheet described by a TextureAtlas XML file (`*.xml`), sprites looked up by name
+#[derive(Asset, TypePath, Debug, Clone)]
+pub struct SpriteSheet {
+    #[dependency]
+    pub atlas: Handle<TextureAtlas>,
+    /// atlas index by sub-texture name, e.g. `"playerShip1_blue.png"`
+    pub names: HashMap<String, usize>,
+}
+
+impl SpriteSheet {
+    pub fn index(&self, name: &str) -> Result<usize, String> {
+        self.names
+            .get(name)
+            .copied()
+            .ok_or_else(|| format!("no sprite {name:?}"))
+    }
+
+    pub fn indices(&self, names: &[&str]) -> Result<Vec<usize>, String> {
+        names.iter().map(|name| self.index(name)).collect()
+    }
+}
+
+/// Asset loader for the `<TextureAtlas imagePath>` of `<SubTexture name x y width height>`
+/// format of the Kenney packs, the image is loaded next to the XML file
+#[derive(Default)]
+pub struct SpriteSheetLoader;
+
+#[derive(Debug)]
+pub enum SpriteSheetLoaderError {
+    Io(std::io::Error),
+    Xml(xml::reader::Error),
+    Format(String),
+    Image(Box<LoadDirectError>),
+}
+
+impl fmt::Display for SpriteSheetLoaderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "cannot read asset: {err}"),
+            Self::Xml(err) => write!(f, "cannot parse asset: {err}"),
+            Self::Format(err) => write!(f, "invalid sprite sheet: {err}"),
+            Self::Image(err) => write!(f, "cannot load sprite sheet image: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for SpriteSheetLoaderError {}
+
+fn attribute<'a>(
+    attributes: &'a [OwnedAttribute],
+    name: &str,
+) -> Result<&'a str, SpriteSheetLoaderError> {
+    attributes
+        .iter()
+        .find(|attribute| attribute.name.local_name == name)
+        .map(|attribute| attribute.value.as_str())
+        .ok_or_else(|| SpriteSheetLoaderError::Format(format!("missing attribute {name:?}")))
+}
+
+fn number(attributes: &[OwnedAttribute], name: &str) -> Result<f32, SpriteSheetLoaderError> {
+    let value = attribute(attributes, name)?;
+    value
+        .parse()
+        .map_err(|_| SpriteSheetLoaderError::Format(format!("{name}={value:?} is not a number")))
+}
+
+/// Image path and named sub-texture rectangles of a sheet XML file
+fn parse_sheet(bytes: &[u8]) -> Result<(String, Vec<(String, Rect)>), SpriteSheetLoaderError> {
+    let mut image_path = None;
+    let mut sub_textures = Vec::new();
+    for event in EventReader::new(bytes) {
+        let XmlEvent::StartElement {
+            name, attributes, ..
+        } = event.map_err(SpriteSheetLoaderError::Xml)?
+        else {
+            continue;
+        };
+        match name.local_name.as_str() {
+            "TextureAtlas" => {
+                image_path = Some(attribute(&attributes, "imagePath")?.to_string());
+            }
+            "SubTexture" => {
+                let name = attribute(&attributes, "name")?.to_string();
+                let (x, y) = (number(&attributes, "x")?, number(&attributes, "y")?);
+                let (width, height) = (
+                    number(&attributes, "width")?,
+                    number(&attributes, "height")?,
+                );
+                // checked before `Rect::new`, which would swap the corners
+                if x < 0. || y < 0. || width <= 0. || height <= 0. {
+                    return Err(SpriteSheetLoaderError::Format(format!(
+                        "{name:?} has a negative position or an empty size"
+                    )));
+                }
+                sub_textures.push((name, Rect::new(x, y, x + width, y + height)));
+            }
+            _ => {}
+        }
+    }
+    let image_path = image_path
+        .ok_or_else(|| SpriteSheetLoaderError::Format("no TextureAtlas element".into()))?;
+    Ok((image_path, sub_textures))
+}
+
+impl AssetLoader for SpriteSheetLoader {
+    type Asset = SpriteSheet;
+    type Settings = ();
+    type Error = SpriteSheetLoaderError;
+
+    fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader,
+        _settings: &'a Self::Settings,
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<Self::Asset, Self::Error>> {
+        Box::pin(async move {
+            let mut bytes = Vec::new();
+            reader
+                .read_to_end(&mut bytes)
+                .await
+                .map_err(SpriteSheetLoaderError::Io)?;
+            let (image_path, sub_textures) = parse_sheet(&bytes)?;
+
+            // the atlas needs the image size, so the image is loaded right away
+            let image_path = load_context
+                .path()
+                .parent()
+                .unwrap_or(std::path::Path::new(""))
+                .join(image_path);
+            let image = load_context
+                .load_direct(image_path)
+                .await
+                .map_err(|err| SpriteSheetLoaderError::Image(Box::new(err)))?
+                .take::<Image>()
+                .ok_or_else(|| {
+                    SpriteSheetLoaderError::Format("imagePath is not an image".into())
+                })?;
+            let size = image.size().as_vec2();
+            let texture = load_context.add_labeled_asset("image".into(), image);
+
+            let mut atlas = TextureAtlas::new_empty(texture, size);
+            let mut names = HashMap::new();
+            for (name, rect) in sub_textures {
+                if rect.max.x > size.x || rect.max.y > size.y {
+                    return Err(SpriteSheetLoaderError::Format(format!(
+                        "{name:?} lies outside the image"
+                    )));
+                }
+                names.insert(name, atlas.add_texture(rect));
+            }
+            Ok(SpriteSheet {
+                atlas: load_context.add_labeled_asset("atlas".into(), atlas),
+                names,
+            })
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["xml"]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(sub_textures: &str) -> Result<(String, Vec<(String, Rect)>), SpriteSheetLoaderError> {
+        parse_sheet(
+            format!(r#"<?xml version="1.0"?><TextureAtlas imagePath="sheet.png">{sub_textures}</TextureAtlas>"#)
+                .as_bytes(),
+        )
+    }
+
+    #[test]
+    fn parses_sub_textures() {
+        let (image_path, sub_textures) = parse(
+            r#"<SubTexture name="a.png" x="0" y="78" width="222" height="39"/>
+               <SubTexture name="b.png" x="10" y="0" width="5" height="6"/>"#,
+        )
+        .unwrap();
+        assert_eq!(image_path, "sheet.png");
+        assert_eq!(
+            sub_textures,
+            vec![
+                ("a.png".to_string(), Rect::new(0., 78., 222., 117.)),
+                ("b.png".to_string(), Rect::new(10., 0., 15., 6.)),
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_bad_sub_textures() {
+        for sub_texture in [
+            r#"<SubTexture name="a.png" x="-1" y="0" width="5" height="6"/>"#,
+            r#"<SubTexture name="a.png" x="0" y="0" width="0" height="6"/>"#,
+            r#"<SubTexture name="a.png" x="0" y="0" width="5" height="-6"/>"#,
+            r#"<SubTexture name="a.png" x="0" y="0" width="five" height="6"/>"#,
+            r#"<SubTexture x="0" y="0" width="5" height="6"/>"#,
+        ] {
+            assert!(matches!(
+                parse(sub_texture),
+                Err(SpriteSheetLoaderError::Format(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn rejects_missing_texture_atlas_and_bad_xml() {
+        assert!(matches!(
+            parse_sheet(b"<Sheet/>"),
+            Err(SpriteSheetLoaderError::Format(_))
+        ));
+        assert!(matches!(
+            parse_sheet(b"<TextureAtlas imagePath=\"sheet.png\">"),
+            Err(SpriteSheetLoaderError::Xml(_))
+        ));
+    }
+}
@@ -0,0 +1,165 @@
+use crate::asset::{
+    boss::BossLibrary,
+    enemy::EnemyArchetypes,
+    formation::{FormationKind, FormationSpec},
+    path::PathLibrary,
+};
+use bevy::{prelude::*, reflect::TypePath};
+use serde::Deserialize;
+
+/// Asset - Wave script (`*.waves.ron`)
+#[derive(Asset, TypePath, Deserialize, Debug, Clone)]
+pub struct WaveScript {
+    /// seconds between a cleared wave and the next one
+    pub intermission: f32,
+    pub waves: Vec<WaveDef>,
+    /// scaling of the waves past the end of the script
+    pub endless: WaveScaling,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct WaveDef {
+    /// archetype names and their weight, empty picks by archetype weight
+    #[serde(default)]
+    pub enemies: Vec<(String, f32)>,
+    /// formation kinds to pick from, empty spawns pairs of orbiting enemies
+    #[serde(default)]
+    pub kinds: Vec<FormationSpec>,
+    pub formations: u32,
+    /// seconds between two formation spawns
+    pub cadence: f32,
+    /// no new formation spawns while this many enemies are alive
+    pub max_alive: u32,
+    pub clear: WaveClear,
+    /// boss spawned when the wave starts
+    #[serde(default)]
+    pub boss: Option<String>,
+    /// seconds between two meteor spawns, 0 for none
+    #[serde(default)]
+    pub meteors: f32,
+}
+
+/// When a wave counts as cleared
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum WaveClear {
+    /// every enemy of the wave was spawned and none is left
+    Destroyed,
+    /// the given seconds have passed, or every enemy is gone before that
+    Survive(f32),
+}
+
+/// Growth per wave past the script, applied to the last scripted wave
+#[derive(Deserialize, Debug, Clone)]
+pub struct WaveScaling {
+    pub formations: f32,
+    /// cadence multiplier
+    pub cadence: f32,
+    pub min_cadence: f32,
+    pub max_alive: f32,
+    pub max_alive_cap: u32,
+    /// every `boss_every` waves past the script is a boss wave, 0 for none
+    #[serde(default)]
+    pub boss_every: u32,
+    /// bosses of those waves, in turn
+    #[serde(default)]
+    pub bosses: Vec<String>,
+}
+
+impl WaveScript {
+    /// Definition of `wave` (1 based), scripted or scaled from the last scripted one
+    pub fn wave(&self, wave: u32) -> Option<WaveDef> {
+        let index = wave.checked_sub(1)? as usize;
+        if let Some(def) = self.waves.get(index) {
+            return Some(def.clone());
+        }
+        let extra = index + 1 - self.waves.len();
+        let scaling = &self.endless;
+        // a boss ends every set of `boss_every` waves
+        let boss_every = scaling.boss_every as usize;
+        if boss_every > 0 && extra % boss_every == 0 && !scaling.bosses.is_empty() {
+            let boss = &scaling.bosses[(extra / boss_every - 1) % scaling.bosses.len()];
+            return Some(WaveDef {
+                enemies: Vec::new(),
+                kinds: Vec::new(),
+                formations: 0,
+                cadence: 1.,
+                max_alive: 0,
+                clear: WaveClear::Destroyed,
+                boss: Some(boss.clone()),
+                meteors: 0.,
+            });
+        }
+        let last = self.waves.iter().rev().find(|def| def.boss.is_none())?;
+        let extra = extra as f32;
+        Some(WaveDef {
+            enemies: last.enemies.clone(),
+            kinds: last.kinds.clone(),
+            formations: last.formations + (scaling.formations * extra) as u32,
+            cadence: (last.cadence * scaling.cadence.powf(extra)).max(scaling.min_cadence),
+            max_alive: (last.max_alive + (scaling.max_alive * extra) as u32)
+                .min(scaling.max_alive_cap),
+            clear: last.clear,
+            boss: None,
+            meteors: last.meteors,
+        })
+    }
+
+    /// Check every wave has valid formations and only names known archetypes, paths and bosses
+    pub fn validate(
+        &self,
+        archetypes: &EnemyArchetypes,
+        paths: &PathLibrary,
+        bosses: &BossLibrary,
+    ) -> Result<(), String> {
+        if self.waves.iter().all(|def| def.boss.is_some()) {
+            return Err("the wave script has no regular waves".to_owned());
+        }
+        if self.endless.max_alive_cap == 0 {
+            return Err("endless max_alive_cap must be positive".to_owned());
+        }
+        let wave_bosses = self.waves.iter().filter_map(|def| def.boss.as_ref());
+        for name in wave_bosses.chain(self.endless.bosses.iter()) {
+            if bosses.get(name).is_none() {
+                return Err(format!("unknown boss {name:?}"));
+            }
+        }
+        for (index, def) in self.waves.iter().enumerate() {
+            if def.meteors < 0. {
+                return Err(format!("wave {} has a negative meteor cadence", index + 1));
+            }
+            if def.formations > 0 && def.max_alive == 0 {
+                return Err(format!(
+                    "wave {} spawns formations but max_alive is 0",
+                    index + 1
+                ));
+            }
+            for (name, weight) in def.enemies.iter() {
+                if archetypes.get(name).is_none() {
+                    return Err(format!("wave {} uses unknown enemy {name:?}", index + 1));
+                }
+                if *weight <= 0. {
+                    return Err(format!(
+                        "wave {} enemy {name:?} needs a positive weight",
+                        index + 1
+                    ));
+                }
+            }
+            for spec in def.kinds.iter() {
+                if spec.weight <= 0. {
+                    return Err(format!(
+                        "wave {} formation kinds need positive weights",
+                        index + 1
+                    ));
+                }
+                spec.validate()
+                    .map_err(|err| format!("wave {}: {err}", index + 1))?;
+                if let FormationKind::Path { path, .. } = &spec.kind {
+                    if paths.get(path).is_none() {
+                        return Err(format!("wave {} uses unknown path {path:?}", index + 1));
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,105 @@
+use crate::{asset::hitbox::HitboxShape, component::sprite::SpriteSize};
+use bevy::{prelude::*, reflect::TypePath, utils::HashSet};
+use serde::Deserialize;
+
+/// Asset - Player weapons (`*.weapons.ron`), the first one is the starting weapon
+#[derive(Asset, TypePath, Deserialize, Debug, Clone)]
+pub struct WeaponLibrary {
+    pub weapons: Vec<WeaponDef>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct WeaponDef {
+    pub kind: WeaponKind,
+    pub name: String,
+    /// projectile sprite, its name in Spritesheet/sheet.xml
+    pub sprite: String,
+    /// projectile sprite size (unscaled pixels)
+    pub size: (f32, f32),
+    #[serde(default)]
+    pub hitbox: HitboxShape,
+    /// stats by weapon level, the last one is used above it
+    pub levels: Vec<WeaponStats>,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WeaponKind {
+    Laser,
+    Spread,
+    /// projectiles go through every enemy they hit
+    Beam,
+    /// projectiles turn towards the nearest enemy
+    Missiles,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy)]
+pub struct WeaponStats {
+    /// shots per second while fire is held
+    pub fire_rate: f32,
+    /// hit points each projectile takes off
+    pub damage: u32,
+    /// projectiles per shot
+    pub spread_count: u32,
+    /// degrees the projectiles fan over, side by side when 0
+    #[serde(default)]
+    pub spread: f32,
+    /// projectile speed, units per second
+    pub speed: f32,
+    /// degrees per second missiles turn towards their target
+    #[serde(default)]
+    pub turn_rate: f32,
+}
+
+impl WeaponDef {
+    pub fn sprite_size(&self) -> SpriteSize {
+        SpriteSize {
+            w: self.size.0,
+            h: self.size.1,
+        }
+    }
+
+    /// Stats at `level`, counted from 1
+    pub fn stats(&self, level: u32) -> WeaponStats {
+        let index = (level.max(1) as usize - 1).min(self.levels.len() - 1);
+        self.levels[index]
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        self.hitbox.validate()?;
+        if self.levels.is_empty() {
+            return Err("weapon has no levels".into());
+        }
+        for stats in self.levels.iter() {
+            if stats.fire_rate <= 0. || stats.spread_count == 0 {
+                return Err("weapon needs a positive fire rate and spread count".into());
+            }
+            if self.kind == WeaponKind::Missiles && stats.turn_rate <= 0. {
+                return Err("missiles need a positive turn rate".into());
+            }
+        }
+        Ok(())
+    }
+}
+
+impl WeaponLibrary {
+    pub fn get(&self, kind: WeaponKind) -> Option<&WeaponDef> {
+        self.weapons.iter().find(|weapon| weapon.kind == kind)
+    }
+
+    /// Check there is a starting weapon, every kind is defined once and every weapon
+    pub fn validate(&self) -> Result<(), String> {
+        if self.weapons.is_empty() {
+            return Err("no starting weapon".into());
+        }
+        let mut kinds = HashSet::new();
+        for weapon in self.weapons.iter() {
+            if !kinds.insert(weapon.kind) {
+                return Err(format!("weapon {:?} defined twice", weapon.kind));
+            }
+            weapon
+                .validate()
+                .map_err(|err| format!("weapon {:?}: {err}", weapon.name))?;
+        }
+        Ok(())
+    }
+}
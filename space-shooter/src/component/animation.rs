@@ -0,0 +1,51 @@
+use bevy::prelude::*;
+
+/// Component - Flips the `TextureAtlasSprite` through `frames` at `fps`
+#[derive(Component, Debug, Clone)]
+pub struct Animation {
+    /// atlas indices, in play order
+    pub frames: Vec<usize>,
+    pub fps: f32,
+    pub mode: AnimationMode,
+    /// release the entity once a `Once` animation is over
+    pub despawn: bool,
+    /// seconds since the animation started
+    pub elapsed: f32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnimationMode {
+    /// start over after the last frame
+    Loop,
+    /// play every frame a single time
+    Once,
+}
+
+impl Animation {
+    pub fn new(frames: Vec<usize>, fps: f32, mode: AnimationMode) -> Self {
+        Self {
+            frames,
+            fps,
+            mode,
+            despawn: false,
+            elapsed: 0.,
+        }
+    }
+
+    pub fn despawn_on_finish(mut self) -> Self {
+        self.despawn = true;
+        self
+    }
+
+    /// Atlas index to show, `None` once a `Once` animation is over
+    pub fn frame(&self) -> Option<usize> {
+        let step = (self.elapsed * self.fps) as usize;
+        match self.mode {
+            AnimationMode::Loop if !self.frames.is_empty() => {
+                Some(self.frames[step % self.frames.len()])
+            }
+            AnimationMode::Loop => None,
+            AnimationMode::Once => self.frames.get(step).copied(),
+        }
+    }
+}
@@ -0,0 +1,5 @@
+use bevy::prelude::*;
+
+/// Marker component identifier Background
+#[derive(Component, Default, Debug)]
+pub struct Background;
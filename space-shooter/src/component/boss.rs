@@ -0,0 +1,75 @@
+use crate::{
+    asset::boss::{BossDef, BossPhase},
+    component::fomation::FormationMotion,
+};
+use bevy::prelude::*;
+
+/// Component - Boss, runs through the phases of its definition
+#[derive(Component, Debug, Clone)]
+pub struct Boss {
+    pub name: String,
+    pub phases: Vec<BossPhase>,
+    pub phase: usize,
+    /// seconds until each beam of the current phase fires
+    pub cooldowns: Vec<f32>,
+    /// path movement, started once the boss reached the start of the path
+    pub pending: Option<FormationMotion>,
+}
+
+impl Boss {
+    pub fn new(def: &BossDef) -> Self {
+        let mut boss = Self {
+            name: def.name.clone(),
+            phases: def.phases.clone(),
+            phase: 0,
+            cooldowns: Vec::new(),
+            pending: None,
+        };
+        boss.enter_phase(0);
+        boss
+    }
+
+    pub fn current(&self) -> &BossPhase {
+        &self.phases[self.phase]
+    }
+
+    /// Switch to `phase`, every beam waits one interval before it fires
+    pub fn enter_phase(&mut self, phase: usize) {
+        self.phase = phase;
+        self.cooldowns = self.phases[phase]
+            .beams
+            .iter()
+            .map(|beam| beam.interval)
+            .collect();
+        self.pending = None;
+    }
+
+    /// Phase matching the remaining health fraction, phases never go back
+    pub fn phase_for(&self, health: f32) -> usize {
+        self.phases
+            .iter()
+            .rposition(|phase| health <= phase.threshold)
+            .map_or(self.phase, |phase| phase.max(self.phase))
+    }
+}
+
+/// Component - Beam sweeping below a boss, made of `BossBeamSegment` entities
+#[derive(Component, Debug, Clone)]
+pub struct BossBeam {
+    pub boss: Entity,
+    pub segments: Vec<Entity>,
+    /// sweep angle, degrees
+    pub sweep: f32,
+    pub warmup: f32,
+    pub duration: f32,
+    pub elapsed: f32,
+    /// true once the warmup is over and the segments hurt
+    pub active: bool,
+}
+
+/// Component - Segment of a `BossBeam`, stays when it hits a ship and hits each ship once
+#[derive(Component, Default, Debug)]
+pub struct BossBeamSegment {
+    /// ships already hit
+    pub hit: Vec<Entity>,
+}
@@ -0,0 +1,49 @@
+use bevy::prelude::*;
+use std::ops::BitOr;
+
+/// Set of collision layers
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Layers(pub u8);
+
+impl Layers {
+    pub const NONE: Self = Self(0);
+    pub const PLAYER: Self = Self(1);
+    pub const ENEMY: Self = Self(1 << 1);
+    pub const PLAYER_PROJECTILE: Self = Self(1 << 2);
+    pub const ENEMY_PROJECTILE: Self = Self(1 << 3);
+    pub const PICKUP: Self = Self(1 << 4);
+    pub const HAZARD: Self = Self(1 << 5);
+
+    pub fn intersects(self, other: Self) -> bool {
+        self.0 & other.0 != 0
+    }
+}
+
+impl BitOr for Layers {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+/// Component - Takes part in the collision detection, sized by its `SpriteSize`
+///
+/// A `Collision` event is sent to the entity for everything on a layer of its `mask`
+/// it overlaps.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Collider {
+    pub layer: Layers,
+    pub mask: Layers,
+}
+
+impl Collider {
+    pub fn new(layer: Layers, mask: Layers) -> Self {
+        Self { layer, mask }
+    }
+
+    /// Only hit by others, like projectiles
+    pub fn passive(layer: Layers) -> Self {
+        Self::new(layer, Layers::NONE)
+    }
+}
@@ -0,0 +1,18 @@
+use crate::HIT_FLASH_TIME;
+use bevy::prelude::*;
+
+/// Component - Hit points a laser takes off what it hits
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Damage(pub u32);
+
+/// Component - Tints the sprite for `remaining` seconds after a hit
+#[derive(Component, Default, Debug, Clone, Copy)]
+pub struct HitFlash {
+    pub remaining: f32,
+}
+
+impl HitFlash {
+    pub fn hit(&mut self) {
+        self.remaining = HIT_FLASH_TIME;
+    }
+}
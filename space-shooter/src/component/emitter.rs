@@ -0,0 +1,51 @@
+use crate::asset::pattern::BulletPattern;
+use bevy::prelude::*;
+
+/// Component - Fires its bullet patterns, each on its own cooldown
+#[derive(Component, Debug, Clone)]
+pub struct BulletEmitter {
+    pub patterns: Vec<BulletPattern>,
+    pub states: Vec<EmitterState>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct EmitterState {
+    /// seconds until the next volley
+    pub cooldown: f32,
+    /// volleys fired in the current burst
+    pub shots: u32,
+    /// spiral rotation, radians
+    pub turn: f32,
+}
+
+impl BulletEmitter {
+    /// Every pattern waits one interval before it fires
+    pub fn new(patterns: Vec<BulletPattern>) -> Self {
+        let states = patterns
+            .iter()
+            .map(|pattern| EmitterState {
+                cooldown: pattern.interval,
+                ..Default::default()
+            })
+            .collect();
+        Self { patterns, states }
+    }
+
+    /// Hold every pattern back `delay` more seconds
+    pub fn with_delay(mut self, delay: f32) -> Self {
+        for state in self.states.iter_mut() {
+            state.cooldown += delay;
+        }
+        self
+    }
+}
+
+/// Component - Projectile turning towards its target, the player for enemy bullets and
+/// the nearest enemy for player missiles
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Homing {
+    /// radians per second
+    pub turn_rate: f32,
+    /// seconds left before it flies straight on
+    pub remaining: f32,
+}
@@ -10,3 +10,9 @@ pub struct EnemyFire;
 
 #[derive(Component, Default, Debug)]
 pub struct FromEnemy;
+
+/// Component - Enemy archetype values needed after spawn
+#[derive(Component, Debug, Clone)]
+pub struct EnemyStats {
+    pub score: u32,
+}
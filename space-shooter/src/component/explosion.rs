@@ -4,11 +4,10 @@ use bevy::prelude::*;
 #[derive(Component, Default, Debug)]
 pub struct Explosion;
 
-#[derive(Component, Debug)]
-pub struct ExplosionTimer(pub Timer);
-
-impl Default for ExplosionTimer {
-    fn default() -> Self {
-        Self(Timer::from_seconds(0.05, TimerMode::Repeating))
-    }
+/// What blew up, each plays its own explosion frames
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ExplosionKind {
+    Enemy,
+    Player,
+    Meteor,
 }
@@ -1,51 +1,91 @@
-use bevy::{
-    prelude::{Component, Resource},
-    window::Window,
+use crate::{
+    asset::{
+        formation::{FormationKind, FormationSpec},
+        path::PathLibrary,
+    },
+    component::path::{PathFollower, SampledPath},
+    resource::playfield::PlayField,
+    PLAYFIELD_MIN_SIZE,
 };
-use rand::{thread_rng, Rng};
+use bevy::prelude::{Component, Resource, Vec2};
+use rand::Rng;
+use std::sync::Arc;
 
 /// Component - Enemy Formation (per enemy)
 #[derive(Clone, Component)]
 pub struct Formation {
     pub start: (f32, f32),
-    pub radius: (f32, f32),
-    pub pivot: (f32, f32),
     pub speed: f32,
-    pub angle: f32, // change per tick
+    /// seconds since the member started moving, negative while it waits for its turn
+    pub elapsed: f32,
+    pub motion: FormationMotion,
+}
+
+/// How a formation member moves, resolved from its `FormationKind`
+#[derive(Clone, Debug)]
+pub enum FormationMotion {
+    /// ellipse around `pivot`
+    Orbit {
+        radius: (f32, f32),
+        pivot: (f32, f32),
+        angle: f32, // change per tick
+    },
+    /// fly to `slot`, then sway side to side by up to `sway` units
+    Hold { slot: Vec2, sway: f32 },
+    /// cross the field along a sine wave, `dir` 1 for left to right
+    Sine {
+        y: f32,
+        amplitude: f32,
+        wavelength: f32,
+        dir: f32,
+    },
+    /// fly to `slot`, wait `hold` seconds, then dive through the bottom and come back
+    Dive {
+        slot: Vec2,
+        hold: f32,
+        /// x the dive aims at, `None` while in the slot
+        target: Option<f32>,
+    },
+    /// follow a path from the path library
+    Path(PathFollower),
 }
 
 /// Resource - Formation Maker
 #[derive(Default, Resource)]
 pub struct FormationMaker {
-    current_template: Option<Formation>,
-    current_members: u32,
+    /// formations made so far, alternates the entry side
+    made: u32,
 }
 
 /// Formation factory implementation
 impl FormationMaker {
-    pub fn make(&mut self, win: &Window) -> Formation {
-        let formation_members_max = 2;
-        let base_speed = 500.;
-        let (ww, wh) = (win.resolution.width(), win.resolution.height());
-        match (
-            &self.current_template,
-            self.current_members >= formation_members_max,
-        ) {
-            // if has current template and still within max members
-            (Some(tmpl), false) => {
-                self.current_members += 1;
-                tmpl.clone()
-            }
-            // if first formation or previous formation is full (need to create a new one)
-            (None, _) | (_, true) => {
-                let mut rng = thread_rng();
+    /// Make one `Formation` per member of `spec`
+    pub fn make(
+        &mut self,
+        spec: &FormationSpec,
+        speed: f32,
+        paths: &PathLibrary,
+        playfield: &PlayField,
+        rng: &mut impl Rng,
+    ) -> Vec<Formation> {
+        // the spawn ranges below are empty on smaller playfields
+        let (ww, wh) = (
+            playfield.w.max(PLAYFIELD_MIN_SIZE.0),
+            playfield.h.max(PLAYFIELD_MIN_SIZE.1),
+        );
+        let side = if self.made % 2 == 0 { 1. } else { -1. };
+        self.made += 1;
+        let members = spec.members.max(1);
+        // members following the same path wait until the one ahead is `spacing` away
+        let trail = |index: u32| -(index as f32) * spec.spacing / speed;
 
+        match &spec.kind {
+            FormationKind::Orbit => {
                 // compute the start x/y
                 let w_span = ww / 2. + 100.;
                 let h_span = wh / 2. + 100.;
                 let x = if rng.gen_bool(0.5) { w_span } else { -w_span };
                 let y = rng.gen_range(-h_span..h_span);
-                let start = (x, y);
 
                 // compute the pivot x/y
                 let w_span = ww / 4.;
@@ -58,24 +98,120 @@ impl FormationMaker {
                 // compute the start angle
                 let angle = (y - pivot.1).atan2(x - pivot.0);
 
-                // speed (fixed for now)
-                let speed = base_speed;
-
-                // create the formation
-                let formation = Formation {
-                    start,
-                    radius,
-                    pivot,
-                    speed,
-                    angle,
+                (0..members)
+                    .map(|index| Formation {
+                        start: (x, y),
+                        speed,
+                        elapsed: trail(index),
+                        motion: FormationMotion::Orbit {
+                            radius,
+                            pivot,
+                            angle,
+                        },
+                    })
+                    .collect()
+            }
+            FormationKind::V => {
+                let leader = Vec2::new(
+                    rng.gen_range(-ww / 4.0..ww / 4.),
+                    rng.gen_range(wh / 8.0..wh / 4.),
+                );
+                (0..members)
+                    .map(|index| {
+                        // leader in front, then alternating left/right wings
+                        let row = ((index + 1) / 2) as f32;
+                        let wing = if index % 2 == 0 { 1. } else { -1. };
+                        let slot = leader + Vec2::new(wing * row, row * 0.8) * spec.spacing;
+                        Formation {
+                            start: (slot.x, slot.y + wh / 2.),
+                            speed,
+                            elapsed: 0.,
+                            motion: FormationMotion::Hold { slot, sway: 40. },
+                        }
+                    })
+                    .collect()
+            }
+            FormationKind::Grid { columns } => {
+                let columns = (*columns).clamp(1, members);
+                let rows = (members + columns - 1) / columns;
+                let half = Vec2::new(columns as f32 - 1., rows as f32 - 1.) * spec.spacing / 2.;
+                let center = Vec2::new(0., rng.gen_range(wh / 8.0..wh / 4.));
+                // the whole block strafes up to the playfield edges
+                let sway = (ww / 2. - half.x - 60.).max(0.);
+                (0..members)
+                    .map(|index| {
+                        let cell = Vec2::new((index % columns) as f32, (index / columns) as f32);
+                        let slot = center + cell * spec.spacing - half;
+                        Formation {
+                            start: (slot.x, slot.y + wh / 2.),
+                            speed,
+                            elapsed: 0.,
+                            motion: FormationMotion::Hold { slot, sway },
+                        }
+                    })
+                    .collect()
+            }
+            FormationKind::Sine {
+                amplitude,
+                wavelength,
+            } => {
+                let x = -side * (ww / 2. + 100.);
+                let y = rng.gen_range(0.0..wh / 3.);
+                (0..members)
+                    .map(|index| Formation {
+                        start: (x, y),
+                        speed,
+                        elapsed: trail(index),
+                        motion: FormationMotion::Sine {
+                            y,
+                            amplitude: *amplitude,
+                            wavelength: *wavelength,
+                            dir: side,
+                        },
+                    })
+                    .collect()
+            }
+            FormationKind::Dive => {
+                let y = rng.gen_range(wh / 4.0..wh / 3.);
+                let start = (-side * (ww / 2. + 100.), wh / 2. + 100.);
+                (0..members)
+                    .map(|index| {
+                        let x = (index as f32 - (members - 1) as f32 / 2.) * spec.spacing;
+                        Formation {
+                            start,
+                            speed,
+                            elapsed: trail(index),
+                            motion: FormationMotion::Dive {
+                                slot: Vec2::new(x, y),
+                                hold: 2. + index as f32 * 1.5,
+                                target: None,
+                            },
+                        }
+                    })
+                    .collect()
+            }
+            FormationKind::Path { path, mode } => {
+                let Some(def) = paths.get(path) else {
+                    return Vec::new();
                 };
-
-                // store as template
-                self.current_template = Some(formation.clone());
-                // reset members to 1
-                self.current_members = 1;
-
-                formation
+                // every other path formation enters mirrored
+                let path = Arc::new(SampledPath::from_def(
+                    def,
+                    Vec2::new(side * ww / 2., wh / 2.),
+                ));
+                let start = path.start();
+                (0..members)
+                    .map(|index| Formation {
+                        start: (start.x, start.y),
+                        speed,
+                        elapsed: trail(index),
+                        motion: FormationMotion::Path(PathFollower::new(
+                            path.clone(),
+                            speed,
+                            *mode,
+                        )),
+                    })
+                    .collect()
             }
         }
     }
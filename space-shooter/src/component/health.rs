@@ -0,0 +1,20 @@
+use bevy::prelude::*;
+
+/// Component - Hit points, the entity is destroyed when they run out
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Health {
+    pub hp: u32,
+    pub max: u32,
+}
+
+impl Health {
+    pub fn new(hp: u32) -> Self {
+        Self { hp, max: hp }
+    }
+
+    /// Apply `amount` damage, true once no hit points are left
+    pub fn damage(&mut self, amount: u32) -> bool {
+        self.hp = self.hp.saturating_sub(amount);
+        self.hp == 0
+    }
+}
@@ -0,0 +1,223 @@
+use crate::{asset::hitbox::HitboxShape, component::sprite::SpriteSize};
+use bevy::prelude::*;
+
+/// Component - Collision shape, the whole `SpriteSize` rectangle when missing
+#[derive(Component, Debug, Clone, Default)]
+pub struct Hitbox(pub HitboxShape);
+
+/// Hitbox in world space: every point within `radius` of the convex hull of `points`
+///
+/// A circle is one point, a capsule two and a polygon three or more.
+#[derive(Debug, Clone)]
+pub struct WorldShape {
+    pub points: Vec<Vec2>,
+    pub radius: f32,
+}
+
+impl WorldShape {
+    /// `shape` of a sprite of `size`, placed, rotated and scaled by `transform`
+    pub fn new(shape: &HitboxShape, size: &SpriteSize, transform: &Transform) -> Self {
+        let place = |(x, y): (f32, f32)| transform.transform_point(Vec3::new(x, y, 0.)).truncate();
+        let scale = transform.scale.x.abs().max(transform.scale.y.abs());
+        let (w, h) = (size.w / 2., size.h / 2.);
+        match shape {
+            HitboxShape::Rect | HitboxShape::Mask { .. } => Self {
+                points: [(-w, -h), (w, -h), (w, h), (-w, h)].map(place).to_vec(),
+                radius: 0.,
+            },
+            HitboxShape::Circle { radius, center } => Self {
+                points: vec![place(*center)],
+                radius: radius * scale,
+            },
+            HitboxShape::Capsule { from, to, radius } => Self {
+                points: vec![place(*from), place(*to)],
+                radius: radius * scale,
+            },
+            HitboxShape::Polygon { points } => Self {
+                points: points.iter().copied().map(place).collect(),
+                radius: 0.,
+            },
+        }
+    }
+
+    /// Axis aligned bounding box, min and max corners
+    pub fn bounds(&self) -> (Vec2, Vec2) {
+        let (min, max) = self.points.iter().fold(
+            (Vec2::splat(f32::MAX), Vec2::splat(f32::MIN)),
+            |(min, max), point| (min.min(*point), max.max(*point)),
+        );
+        (min - self.radius, max + self.radius)
+    }
+
+    pub fn intersects(&self, other: &WorldShape) -> bool {
+        if overlap(&self.points, &other.points) {
+            return true;
+        }
+        let reach = self.radius + other.radius;
+        reach > 0. && distance_squared(&self.points, &other.points) <= reach * reach
+    }
+
+    /// Edges of the hull, a single point is one zero length edge
+    pub fn edges(&self) -> impl Iterator<Item = (Vec2, Vec2)> + '_ {
+        edges(&self.points)
+    }
+}
+
+fn edges(points: &[Vec2]) -> impl Iterator<Item = (Vec2, Vec2)> + '_ {
+    let count = match points.len() {
+        // a segment has a single edge, not two
+        2 => 1,
+        len => len,
+    };
+    (0..count).map(|index| (points[index], points[(index + 1) % points.len()]))
+}
+
+/// Separating axis test of two convex hulls
+fn overlap(a: &[Vec2], b: &[Vec2]) -> bool {
+    let axes = |points: &[Vec2]| -> Vec<Vec2> {
+        match points.len() {
+            0 | 1 => Vec::new(),
+            // a segment also separates along itself
+            2 => {
+                let dir = points[1] - points[0];
+                vec![dir, dir.perp()]
+            }
+            _ => edges(points).map(|(from, to)| (to - from).perp()).collect(),
+        }
+    };
+    let axes: Vec<Vec2> = axes(a).into_iter().chain(axes(b)).collect();
+    if axes.is_empty() {
+        return false;
+    }
+    axes.into_iter().all(|axis| {
+        let project = |points: &[Vec2]| {
+            points
+                .iter()
+                .fold((f32::MAX, f32::MIN), |(min, max), point| {
+                    let value = point.dot(axis);
+                    (min.min(value), max.max(value))
+                })
+        };
+        let ((a_min, a_max), (b_min, b_max)) = (project(a), project(b));
+        a_min <= b_max && b_min <= a_max
+    })
+}
+
+/// Smallest squared distance between two hulls that do not overlap
+fn distance_squared(a: &[Vec2], b: &[Vec2]) -> f32 {
+    let one_way = |points: &[Vec2], hull: &[Vec2]| {
+        points
+            .iter()
+            .flat_map(|point| {
+                edges(hull).map(|(from, to)| segment_distance_squared(*point, from, to))
+            })
+            .fold(f32::MAX, f32::min)
+    };
+    one_way(a, b).min(one_way(b, a))
+}
+
+fn segment_distance_squared(point: Vec2, from: Vec2, to: Vec2) -> f32 {
+    let segment = to - from;
+    let length = segment.length_squared();
+    let t = if length > 0. {
+        ((point - from).dot(segment) / length).clamp(0., 1.)
+    } else {
+        0.
+    };
+    point.distance_squared(from + segment * t)
+}
+
+/// Convex hull of `points`, counter-clockwise (monotone chain)
+pub fn convex_hull(mut points: Vec<Vec2>) -> Vec<Vec2> {
+    points.sort_by(|a, b| a.x.total_cmp(&b.x).then(a.y.total_cmp(&b.y)));
+    points.dedup();
+    if points.len() < 3 {
+        return points;
+    }
+    let mut hull: Vec<Vec2> = Vec::with_capacity(points.len() * 2);
+    for pass in [points.clone(), points.into_iter().rev().collect()] {
+        let start = hull.len();
+        for point in pass {
+            while hull.len() >= start + 2
+                && (hull[hull.len() - 1] - hull[hull.len() - 2])
+                    .perp_dot(point - hull[hull.len() - 1])
+                    <= 0.
+            {
+                hull.pop();
+            }
+            hull.push(point);
+        }
+        // the last point starts the other pass
+        hull.pop();
+    }
+    hull
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shape(points: &[(f32, f32)], radius: f32) -> WorldShape {
+        WorldShape {
+            points: points.iter().map(|(x, y)| Vec2::new(*x, *y)).collect(),
+            radius,
+        }
+    }
+
+    fn square(x: f32, y: f32) -> WorldShape {
+        shape(
+            &[
+                (x - 1., y - 1.),
+                (x + 1., y - 1.),
+                (x + 1., y + 1.),
+                (x - 1., y + 1.),
+            ],
+            0.,
+        )
+    }
+
+    #[test]
+    fn polygons_separate_along_an_edge_axis() {
+        assert!(square(0., 0.).intersects(&square(1.5, 1.5)));
+        assert!(!square(0., 0.).intersects(&square(2.5, 0.)));
+        // the bounds overlap, but a diagonal edge of the triangle separates them
+        let triangle = shape(&[(1.2, 0.), (3., 0.), (3., 1.8)], 0.);
+        assert!(!square(0., 0.).intersects(&triangle));
+    }
+
+    #[test]
+    fn circles_and_capsules_reach_by_their_radius() {
+        let circle = shape(&[(1.8, 0.)], 1.);
+        assert!(square(0., 0.).intersects(&circle));
+        assert!(!square(0., 0.).intersects(&shape(&[(2.5, 2.5)], 1.)));
+        let capsule = shape(&[(-5., 1.8), (5., 1.8)], 1.);
+        assert!(square(0., 0.).intersects(&capsule));
+        assert!(!square(0., 0.).intersects(&shape(&[(-5., 3.5), (5., 3.5)], 1.)));
+        assert!(circle.intersects(&shape(&[(3., 0.)], 0.6)));
+        assert!(!circle.intersects(&shape(&[(3.5, 0.)], 0.6)));
+    }
+
+    #[test]
+    fn convex_hull_keeps_the_outer_corners_counter_clockwise() {
+        let points = [
+            (0., 0.),
+            (2., 0.),
+            (1., 1.),
+            (2., 2.),
+            (0., 2.),
+            (1., 0.),
+            (0., 0.),
+        ];
+        let hull = convex_hull(points.iter().map(|(x, y)| Vec2::new(*x, *y)).collect());
+        assert_eq!(
+            hull,
+            vec![
+                Vec2::new(0., 0.),
+                Vec2::new(2., 0.),
+                Vec2::new(2., 2.),
+                Vec2::new(0., 2.)
+            ]
+        );
+        assert_eq!(convex_hull(vec![Vec2::ONE, Vec2::ONE]), vec![Vec2::ONE]);
+    }
+}
@@ -0,0 +1,45 @@
+use bevy::prelude::*;
+
+/// Marker component identifier HUD score text
+#[derive(Component, Default, Debug)]
+pub struct HudScore;
+
+/// Marker component identifier HUD combo text
+#[derive(Component, Default, Debug)]
+pub struct HudCombo;
+
+/// Marker component identifier HUD row of life icons
+#[derive(Component, Default, Debug)]
+pub struct HudLives;
+
+/// Marker component identifier HUD wave number
+#[derive(Component, Default, Debug)]
+pub struct HudWave;
+
+/// Marker component identifier HUD weapon level number
+#[derive(Component, Default, Debug)]
+pub struct HudWeapon;
+
+/// Marker component identifier HUD weapon name text
+#[derive(Component, Default, Debug)]
+pub struct HudWeaponName;
+
+/// Marker component identifier HUD boss health bar, hidden without a boss
+#[derive(Component, Default, Debug)]
+pub struct HudBossBar;
+
+/// Marker component identifier HUD boss health bar fill
+#[derive(Component, Default, Debug)]
+pub struct HudBossFill;
+
+/// Marker component identifier HUD boss name text
+#[derive(Component, Default, Debug)]
+pub struct HudBossName;
+
+/// Marker component identifier HUD row of active power-ups
+#[derive(Component, Default, Debug)]
+pub struct HudPowerUps;
+
+/// Component - HUD timer bar fill of the active power-up named `0`
+#[derive(Component, Default, Debug)]
+pub struct HudPowerUpTimer(pub String);
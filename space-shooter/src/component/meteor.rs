@@ -0,0 +1,10 @@
+use bevy::prelude::*;
+
+/// Component - Meteor hazard, `kind` and `size` index the `MeteorField`
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Meteor {
+    pub kind: usize,
+    pub size: usize,
+    /// radians per second
+    pub spin: f32,
+}
@@ -1,8 +1,24 @@
+pub mod animation;
+pub mod background;
+pub mod boss;
+pub mod collider;
+pub mod damage;
+pub mod emitter;
 pub mod enemy;
 pub mod explosion;
 pub mod fomation;
+pub mod health;
+pub mod hitbox;
+pub mod hud;
 pub mod laser;
+pub mod meteor;
 pub mod movement;
+pub mod path;
+pub mod pickup;
 pub mod player;
+pub mod pool;
+pub mod shield;
 pub mod sprite;
+pub mod state;
 pub mod velocity;
+pub mod weapon;
@@ -0,0 +1,169 @@
+use crate::asset::path::{PathCurve, PathDef, PathMode};
+use bevy::prelude::*;
+use std::sync::Arc;
+
+/// curve samples per segment, enough for the arc length lookup to look smooth
+const PATH_SAMPLES: usize = 24;
+
+/// A curve flattened into a polyline, walked by arc length
+#[derive(Debug, Clone, Default)]
+pub struct SampledPath {
+    points: Vec<Vec2>,
+    /// arc length from the start up to each point
+    lengths: Vec<f32>,
+}
+
+impl SampledPath {
+    pub fn new(curve: PathCurve, points: &[Vec2]) -> Self {
+        let samples: Vec<Vec2> = match curve {
+            PathCurve::CatmullRom => {
+                let segments = points.len().saturating_sub(1);
+                (0..=segments * PATH_SAMPLES)
+                    .map(|step| catmull_rom(points, step as f32 / PATH_SAMPLES as f32))
+                    .collect()
+            }
+            PathCurve::Bezier => {
+                let segments = points.len().saturating_sub(1) / 3;
+                (0..=segments * PATH_SAMPLES)
+                    .map(|step| bezier(points, step as f32 / PATH_SAMPLES as f32))
+                    .collect()
+            }
+        };
+        let mut length = 0.;
+        let lengths = samples
+            .iter()
+            .enumerate()
+            .map(|(index, point)| {
+                if index > 0 {
+                    length += samples[index - 1].distance(*point);
+                }
+                length
+            })
+            .collect();
+        Self {
+            points: samples,
+            lengths,
+        }
+    }
+
+    /// Build `def` in world units, its half extents are scaled by `scale`
+    pub fn from_def(def: &PathDef, scale: Vec2) -> Self {
+        let points: Vec<Vec2> = def
+            .points
+            .iter()
+            .map(|(x, y)| Vec2::new(*x, *y) * scale)
+            .collect();
+        Self::new(def.curve, &points)
+    }
+
+    pub fn length(&self) -> f32 {
+        self.lengths.last().copied().unwrap_or_default()
+    }
+
+    pub fn start(&self) -> Vec2 {
+        self.points.first().copied().unwrap_or_default()
+    }
+
+    /// Point `distance` along the path, clamped to its ends
+    pub fn at(&self, distance: f32) -> Vec2 {
+        let index = self.lengths.partition_point(|length| *length < distance);
+        if index == 0 {
+            return self.start();
+        }
+        let Some(next) = self.points.get(index) else {
+            return self.points.last().copied().unwrap_or_default();
+        };
+        let (from, to) = (self.lengths[index - 1], self.lengths[index]);
+        let t = if to > from {
+            (distance - from) / (to - from)
+        } else {
+            0.
+        };
+        self.points[index - 1].lerp(*next, t)
+    }
+}
+
+/// Component - Moves the entity along a path at a constant speed
+#[derive(Component, Debug, Clone)]
+pub struct PathFollower {
+    pub path: Arc<SampledPath>,
+    /// units per second
+    pub speed: f32,
+    /// distance travelled along the path
+    pub distance: f32,
+    pub mode: PathMode,
+    /// false while going back on a ping-pong path
+    pub forward: bool,
+}
+
+impl PathFollower {
+    pub fn new(path: Arc<SampledPath>, speed: f32, mode: PathMode) -> Self {
+        Self {
+            path,
+            speed,
+            distance: 0.,
+            mode,
+            forward: true,
+        }
+    }
+
+    /// Move `time_step` seconds along the path, `None` once a despawn-at-end path is done
+    pub fn advance(&mut self, time_step: f32) -> Option<Vec2> {
+        let step = self.speed * time_step;
+        self.distance += if self.forward { step } else { -step };
+        let length = self.path.length();
+        match self.mode {
+            PathMode::Loop if length > 0. => self.distance = self.distance.rem_euclid(length),
+            PathMode::Loop => {}
+            PathMode::PingPong => {
+                if self.distance >= length {
+                    self.distance = (2. * length - self.distance).max(0.);
+                    self.forward = false;
+                } else if self.distance <= 0. {
+                    self.distance = (-self.distance).min(length);
+                    self.forward = true;
+                }
+            }
+            PathMode::DespawnAtEnd => {
+                if self.distance > length {
+                    return None;
+                }
+            }
+        }
+        Some(self.path.at(self.distance))
+    }
+}
+
+/// Point at `u` on the Catmull-Rom spline through `points`, `u` goes from 0 to `points.len() - 1`
+pub fn catmull_rom(points: &[Vec2], u: f32) -> Vec2 {
+    let last = points.len().saturating_sub(1);
+    if last == 0 {
+        return points.first().copied().unwrap_or_default();
+    }
+    let u = u.clamp(0., last as f32);
+    let segment = (u as usize).min(last - 1);
+    let t = u - segment as f32;
+    let p0 = points[segment.saturating_sub(1)];
+    let p1 = points[segment];
+    let p2 = points[segment + 1];
+    let p3 = points[(segment + 2).min(last)];
+    let (t2, t3) = (t * t, t * t * t);
+    0.5 * (2. * p1
+        + (p2 - p0) * t
+        + (2. * p0 - 5. * p1 + 4. * p2 - p3) * t2
+        + (3. * p1 - p0 - 3. * p2 + p3) * t3)
+}
+
+/// Point at `u` on chained cubic Bezier segments, `u` goes from 0 to the segment count
+pub fn bezier(points: &[Vec2], u: f32) -> Vec2 {
+    let segments = points.len().saturating_sub(1) / 3;
+    if segments == 0 {
+        return points.first().copied().unwrap_or_default();
+    }
+    let u = u.clamp(0., segments as f32);
+    let segment = (u as usize).min(segments - 1);
+    let t = u - segment as f32;
+    let [p0, p1, p2, p3] = [0, 1, 2, 3].map(|offset| points[segment * 3 + offset]);
+    let s = 1. - t;
+    p0 * s * s * s + p1 * 3. * s * s * t + p2 * 3. * s * t * t + p3 * t * t * t
+}
@@ -0,0 +1,6 @@
+use crate::asset::pickup::PickupDef;
+use bevy::prelude::*;
+
+/// Component - Power-up drifting down until the player picks it up
+#[derive(Component, Debug, Clone)]
+pub struct Pickup(pub PickupDef);
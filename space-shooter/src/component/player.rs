@@ -10,3 +10,13 @@ pub struct PlayerFire;
 
 #[derive(Component, Default, Debug)]
 pub struct FromPlayer;
+
+/// Marker component identifier Player damage overlay
+#[derive(Component, Default, Debug)]
+pub struct PlayerDamage;
+
+/// Component - The ship just respawned, it blinks and goes through hits for `remaining` seconds
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Invulnerable {
+    pub remaining: f32,
+}
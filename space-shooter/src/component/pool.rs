@@ -0,0 +1,8 @@
+use bevy::prelude::*;
+
+/// Component - Sprite entity owned by the `SpritePool`, recycled instead of despawned
+#[derive(Component, Default, Debug, Clone, Copy)]
+pub struct Pooled {
+    /// false while the entity waits hidden in the pool
+    pub active: bool,
+}
@@ -0,0 +1,94 @@
+use bevy::prelude::*;
+
+/// Component - Absorbs up to `max` hits, one hit comes back every `recharge` seconds
+/// without being hit
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Shield {
+    pub hits: u32,
+    pub max: u32,
+    /// seconds per hit recharged, 0 for none
+    pub recharge: f32,
+    /// seconds towards the next recharged hit
+    pub charging: f32,
+}
+
+impl Shield {
+    /// Shield down, recharging
+    pub fn new(max: u32, recharge: f32) -> Self {
+        Self {
+            hits: 0,
+            max,
+            recharge,
+            charging: 0.,
+        }
+    }
+
+    /// Use up a hit, false when the shield is down
+    pub fn absorb(&mut self) -> bool {
+        if self.hits == 0 {
+            return false;
+        }
+        self.hits -= 1;
+        self.charging = 0.;
+        true
+    }
+
+    /// Add `hits` up to the max, returns the hits gained
+    pub fn charge(&mut self, hits: u32) -> u32 {
+        let before = self.hits;
+        self.hits = (self.hits + hits).min(self.max);
+        self.hits - before
+    }
+
+    /// Run the recharge for `delta` seconds, returns the hits gained
+    pub fn recharge(&mut self, delta: f32) -> u32 {
+        if self.recharge <= 0. || self.hits >= self.max {
+            self.charging = 0.;
+            return 0;
+        }
+        self.charging += delta;
+        if self.charging < self.recharge {
+            return 0;
+        }
+        self.charging -= self.recharge;
+        self.charge(1)
+    }
+}
+
+/// Marker component identifier Shield overlay
+#[derive(Component, Default, Debug)]
+pub struct ShieldOverlay;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recharge_gains_a_hit_per_period() {
+        let mut shield = Shield::new(2, 1.);
+        assert_eq!(shield.recharge(0.6), 0);
+        assert_eq!(shield.recharge(0.6), 1);
+        assert!((shield.charging - 0.2).abs() < 1e-5);
+        assert_eq!(shield.recharge(1.), 1);
+        // full, nothing left to charge
+        assert_eq!(shield.recharge(5.), 0);
+        assert_eq!((shield.hits, shield.charging), (2, 0.));
+    }
+
+    #[test]
+    fn a_hit_restarts_the_recharge() {
+        let mut shield = Shield::new(2, 1.);
+        shield.charge(1);
+        shield.recharge(0.9);
+        assert!(shield.absorb());
+        assert_eq!(shield.recharge(0.5), 0);
+        assert!(!shield.absorb());
+    }
+
+    #[test]
+    fn no_recharge_without_a_period() {
+        let mut shield = Shield::new(3, 0.);
+        assert_eq!(shield.recharge(100.), 0);
+        assert_eq!(shield.charge(5), 3);
+    }
+}
@@ -0,0 +1,9 @@
+use bevy::prelude::*;
+
+/// Marker component identifier entities of the running game, cleared on return to the menu
+#[derive(Component, Default, Debug)]
+pub struct InGame;
+
+/// Marker component identifier menu / pause / game over screen, cleared on leaving that state
+#[derive(Component, Default, Debug)]
+pub struct StateScreen;
@@ -1,5 +1,6 @@
 use bevy::prelude::*;
 
+/// Component - Velocity in units per second
 #[derive(Component, Default, Debug)]
 pub struct Velocity {
     pub x: f32,
@@ -0,0 +1,34 @@
+use crate::asset::weapon::{WeaponDef, WeaponKind, WeaponStats};
+use bevy::prelude::*;
+
+/// Component - Weapon of the player ship, fires while Space is held
+#[derive(Component, Debug, Clone)]
+pub struct Weapon {
+    pub kind: WeaponKind,
+    pub level: u32,
+    /// stats of the weapon at its level
+    pub stats: WeaponStats,
+    /// seconds until the next shot
+    pub cooldown: f32,
+}
+
+impl Weapon {
+    pub fn new(def: &WeaponDef, level: u32) -> Self {
+        Self {
+            kind: def.kind,
+            level,
+            stats: def.stats(level),
+            cooldown: 0.,
+        }
+    }
+}
+
+/// Component - Weapons the player has picked up and can cycle through
+#[derive(Component, Debug, Clone)]
+pub struct Arsenal(pub Vec<WeaponKind>);
+
+/// Component - Projectile going through what it hits, each target is hit once
+#[derive(Component, Default, Debug, Clone)]
+pub struct Piercing {
+    pub hit: Vec<Entity>,
+}
@@ -0,0 +1,20 @@
+use bevy::prelude::*;
+
+/// Event - a boss entered the playfield
+#[derive(Event, Debug, Clone)]
+pub struct BossSpawned {
+    pub name: String,
+}
+
+/// Event - a boss crossed a health threshold and started its next phase
+#[derive(Event, Debug, Clone, Copy)]
+pub struct BossPhaseChanged {
+    pub phase: usize,
+    pub position: Vec3,
+}
+
+/// Event - a boss was destroyed
+#[derive(Event, Debug, Clone, Copy)]
+pub struct BossDefeated {
+    pub position: Vec3,
+}
@@ -0,0 +1,8 @@
+use bevy::prelude::*;
+
+/// Event - `entity` overlaps `other`, which is on a layer of the `entity` collider mask
+#[derive(Event, Debug, Clone, Copy)]
+pub struct Collision {
+    pub entity: Entity,
+    pub other: Entity,
+}
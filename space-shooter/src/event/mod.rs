@@ -0,0 +1,5 @@
+pub mod boss;
+pub mod collision;
+pub mod pickup;
+pub mod player;
+pub mod wave;
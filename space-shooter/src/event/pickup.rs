@@ -0,0 +1,16 @@
+use crate::asset::pickup::PickupEffect;
+use bevy::prelude::*;
+
+/// Event - the player picked up a power-up
+#[derive(Event, Debug, Clone)]
+pub struct PowerUpCollected {
+    pub name: String,
+    pub effect: PickupEffect,
+}
+
+/// Event - a timed power-up effect ran out
+#[derive(Event, Debug, Clone)]
+pub struct PowerUpExpired {
+    pub name: String,
+    pub effect: PickupEffect,
+}
@@ -0,0 +1,56 @@
+use bevy::prelude::*;
+
+/// Event - an enemy was destroyed by the player
+#[derive(Event, Debug, Clone, Copy)]
+pub struct EnemyKilled {
+    pub position: Vec3,
+    pub points: u32,
+}
+
+/// Event - the score changed by `points`
+#[derive(Event, Debug, Clone, Copy)]
+pub struct ScoreChanged {
+    pub score: u32,
+    pub points: u32,
+}
+
+/// Event - the combo multiplier went up or was reset
+#[derive(Event, Debug, Clone, Copy)]
+pub struct ComboChanged {
+    pub combo: u32,
+}
+
+/// Event - the number of remaining lives changed
+#[derive(Event, Debug, Clone, Copy)]
+pub struct LivesChanged {
+    pub lives: u32,
+}
+
+/// Event - the player ship was destroyed
+#[derive(Event, Debug, Clone, Copy)]
+pub struct PlayerKilled {
+    pub position: Vec3,
+}
+
+/// Event - the last life is lost
+#[derive(Event, Debug, Clone, Copy)]
+pub struct GameOver {
+    pub score: u32,
+}
+
+/// Event - the player switched weapons or its weapon level changed
+#[derive(Event, Debug, Clone)]
+pub struct WeaponChanged {
+    pub name: String,
+    pub level: u32,
+}
+
+/// Event - the ship shield gained hits, `hits` is what it absorbs now
+#[derive(Event, Debug, Clone, Copy)]
+pub struct ShieldUp {
+    pub hits: u32,
+}
+
+/// Event - the ship shield absorbed its last hit
+#[derive(Event, Debug, Clone, Copy)]
+pub struct ShieldDown;
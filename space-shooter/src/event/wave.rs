@@ -0,0 +1,13 @@
+use bevy::prelude::*;
+
+/// Event - a new enemy wave begins
+#[derive(Event, Debug, Clone, Copy)]
+pub struct WaveStarted {
+    pub wave: u32,
+}
+
+/// Event - the clear condition of a wave was met
+#[derive(Event, Debug, Clone, Copy)]
+pub struct WaveCleared {
+    pub wave: u32,
+}
@@ -1,12 +1,48 @@
-use bevy::{prelude::*, time::common_conditions::on_timer, window::PrimaryWindow};
-use component::sprite::SpriteSize;
+use asset::hitbox::HitboxShape;
+use bevy::{
+    app::AppExit,
+    input::InputPlugin,
+    log::LogPlugin,
+    prelude::*,
+    time::{common_conditions::on_timer, TimeUpdateStrategy},
+    window::WindowResized,
+};
+use component::{background::Background, sprite::SpriteSize};
 use plugin::{
-    collision::CollisionPlugin, enemy::EnemyPlugin, movement::MovementPlugin, player::PlayerPlugin,
+    animation::AnimationPlugin,
+    boss::BossPlugin,
+    collision::CollisionPlugin,
+    data::DataPlugin,
+    debug::DebugPlugin,
+    emitter::EmitterPlugin,
+    enemy::EnemyPlugin,
+    highscore::HighScorePlugin,
+    hud::HudPlugin,
+    meteor::MeteorPlugin,
+    movement::MovementPlugin,
+    path::PathPlugin,
+    pickup::PickupPlugin,
+    player::PlayerPlugin,
+    pool::PoolPlugin,
+    replay::ReplayPlugin,
+    score::ScorePlugin,
+    shield::ShieldPlugin,
+    state::{AppState, StatePlugin},
+    wave::WavePlugin,
+};
+use resource::{
+    config::GameConfig,
+    global::{GameTextures, PlayerState},
+    playfield::PlayField,
+    pool::SpritePool,
+    replay::{Replay, ReplayPlayback, ReplayRecorder},
+    rng::GameRng,
 };
-use resource::global::GameTextures;
 use std::time::Duration;
 
+pub mod asset;
 pub mod component;
+pub mod event;
 pub mod plugin;
 pub mod resource;
 pub mod system;
@@ -14,54 +50,127 @@ pub mod system;
 // region: --- Asset Constants
 const APP_NAME: &str = "space-shooter";
 const WINDOW_SIZE: (f32, f32) = (598., 676.);
+/// smallest playfield, windows shrunk below it clip the playfield
+const PLAYFIELD_MIN_SIZE: (f32, f32) = (300., 300.);
 const SPRITE_SCALE: f32 = 0.5;
-const BACKGROUND: &str = r"Backgrounds\black.png";
+const BACKGROUND: &str = "Backgrounds/black.png";
+// every other sprite is named after its sub-texture of Spritesheet/sheet.xml
 
-const PLAYER_SPRITE: &str = r"PNG\playerShip1_blue.png";
+const PLAYER_SPRITE: &str = "playerShip1_blue.png";
 const PLAYER_SIZE: SpriteSize = SpriteSize { w: 99., h: 75. };
-const PLAYER_LASER_SPRITE: &str = r"PNG\Lasers\laserBlue01.png";
-const PLAYER_LASER_SIZE: SpriteSize = SpriteSize { w: 9., h: 54. };
-const PLAYER_EXPLOSION_SPRITE: &str = r"PNG\Lasers\laserBlue08.png";
-const PLAYER_EXPLOSION_SIZE: SpriteSize = SpriteSize { w: 48., h: 46. };
-
-const ENEMY_SPRITE: &str = r"PNG\Enemies\enemyRed3.png";
-const ENEMY_SIZE: SpriteSize = SpriteSize { w: 103., h: 84. };
-const ENEMY_LASER_SPRITE: &str = r"PNG\Lasers\laserRed01.png";
-const ENEMY_LASER_SIZE: SpriteSize = SpriteSize { w: 9., h: 54. };
-const ENEMY_EXPLOSION_SPRITE: &str = r"PNG\Lasers\laserRed08.png";
-const ENEMY_EXPLOSION_SIZE: SpriteSize = SpriteSize { w: 48., h: 46. };
-
-const PLAYER_SPRITE_BUCKET: [&str; 4] = [
-    r"PNG\playerShip1_blue.png",
-    r"PNG\playerShip1_green.png",
-    r"PNG\playerShip1_orange.png",
-    r"PNG\playerShip1_red.png",
+const PLAYER_HITBOX: HitboxShape = HitboxShape::Mask { threshold: 0.5 };
+const LASER_HITBOX: HitboxShape = HitboxShape::Capsule {
+    from: (0., -22.5),
+    to: (0., 22.5),
+    radius: 4.5,
+};
+const PLAYER_DAMAGE_SPRITES: [&str; 3] = [
+    "playerShip1_damage1.png",
+    "playerShip1_damage2.png",
+    "playerShip1_damage3.png",
 ];
-const ENEMY_SPRITE_BUCKET: [&str; 10] = [
-    r"PNG\Enemies\enemyBlue1.png",
-    r"PNG\Enemies\enemyBlue2.png",
-    r"PNG\Enemies\enemyBlue3.png",
-    r"PNG\Enemies\enemyBlue4.png",
-    r"PNG\Enemies\enemyBlue5.png",
-    r"PNG\Enemies\enemyRed1.png",
-    r"PNG\Enemies\enemyRed2.png",
-    r"PNG\Enemies\enemyRed3.png",
-    r"PNG\Enemies\enemyRed4.png",
-    r"PNG\Enemies\enemyRed5.png",
+const PLAYER_SHIELD_SPRITES: [&str; 3] = ["shield1.png", "shield2.png", "shield3.png"];
+
+const ENEMY_LASER_SPRITE: &str = "laserRed01.png";
+const ENEMY_LASER_SIZE: SpriteSize = SpriteSize { w: 9., h: 54. };
+const ENEMY_ORB_SPRITE: &str = "laserRed10.png";
+const ENEMY_ORB_SIZE: SpriteSize = SpriteSize { w: 30., h: 30. };
+const ENEMY_ORB_HITBOX: HitboxShape = HitboxShape::Circle {
+    radius: 11.,
+    center: (0., 0.),
+};
+
+const BOSS_BEAM_SPRITE: &str = "beamLong2.png";
+const BOSS_BEAM_SIZE: SpriteSize = SpriteSize { w: 30., h: 30. };
+const BOSS_BEAM_SEGMENT_LENGTH: f32 = 32.;
+const BOSS_BEAM_MUZZLE_SPRITE: &str = "beam3.png";
+
+const ENEMY_EXPLOSION_FRAMES: [&str; 4] = [
+    "laserRed08.png",
+    "laserRed09.png",
+    "laserRed11.png",
+    "laserRed10.png",
 ];
-const EXPLOSION_BUCKET: [&str; 3] = [
-    r"PNG\Lasers\laserBlue08.png",
-    r"PNG\Lasers\laserGreen14.png",
-    r"PNG\Lasers\laserRed08.png",
+const PLAYER_EXPLOSION_FRAMES: [&str; 4] = [
+    "laserBlue08.png",
+    "laserBlue09.png",
+    "laserBlue11.png",
+    "laserBlue10.png",
 ];
+const METEOR_EXPLOSION_FRAMES: [&str; 3] =
+    ["laserGreen14.png", "laserGreen15.png", "laserGreen16.png"];
+const PLAYER_ENGINE_FRAMES: [&str; 3] = ["fire08.png", "fire09.png", "fire10.png"];
+/// unscaled width of the biggest explosion frames
+const EXPLOSION_SIZE: f32 = 48.;
+const HUD_FONT: &str = "Bonus/kenvector_future.ttf";
+const HUD_LIFE_SPRITE: &str = "PNG/UI/playerLife1_blue.png";
+const HUD_LIFE_SIZE: SpriteSize = SpriteSize { w: 33., h: 26. };
+const HUD_NUMERAL_SIZE: SpriteSize = SpriteSize { w: 19., h: 19. };
+const HUD_NUMERAL_X_SPRITE: &str = "PNG/UI/numeralX.png";
+const HUD_POWER_UP_SIZE: SpriteSize = SpriteSize { w: 24., h: 24. };
 // endregion: --- Asset Constants
 
+// region: --- Game Constants
+const PLAYER_RESPAWN_DELAY: f64 = 3.;
+const PLAYER_LIVES: u32 = 3;
+const PLAYER_LIVES_MAX: u32 = 5;
+const PLAYER_HP: u32 = 4;
+const PLAYER_SHIELD_HITS: u32 = 3;
+/// seconds per shield hit recharged
+const PLAYER_SHIELD_RECHARGE: f32 = 15.;
+/// default seconds a respawned ship takes no hits, see `--invulnerability`
+const PLAYER_INVULNERABILITY: f32 = 2.;
+/// visibility toggles per second while invulnerable
+const PLAYER_BLINK_RATE: f32 = 10.;
+/// enemy bullets this close to a respawned ship are cleared
+const PLAYER_SPAWN_CLEAR_RADIUS: f32 = 160.;
+/// width side by side projectiles are spread over
+const PLAYER_MUZZLE_WIDTH: f32 = 46.;
+const BOSS_BEAM_DAMAGE: u32 = 2;
+const HIT_FLASH_TIME: f32 = 0.1;
+const HIT_FLASH_COLOR: Color = Color::rgb(1., 0.35, 0.35);
+const COMBO_WINDOW: f64 = 2.;
+const COMBO_MAX: u32 = 8;
+const PICKUP_SPEED: f32 = 120.;
+const COLLISION_CELL_SIZE: f32 = 64.;
+const EXPLOSION_FPS: f32 = 12.;
+const PLAYER_ENGINE_FPS: f32 = 15.;
+const SPRITE_POOL_PREWARM: usize = 256;
+// endregion: --- Game Constants
+
 fn main() {
-    App::new()
-        .add_systems(PreStartup, setup_system)
-        .add_systems(Startup, background_spawn_system)
-        .add_systems(Update, monitor.run_if(on_timer(Duration::from_secs(2))))
-        .add_plugins(DefaultPlugins.set(WindowPlugin {
+    let mut config = GameConfig::from_args().unwrap_or_else(|err| exit_with_usage(&err));
+    let playback = config
+        .replay
+        .as_ref()
+        .map(|path| Replay::load(path).unwrap_or_else(|err| exit_with_usage(&err)));
+    // a replay always runs with its recorded seed, tick rate and invulnerability
+    if let Some(replay) = &playback {
+        config.seed = Some(replay.seed);
+        config.tick_rate = replay.tick_rate;
+        config.invulnerability = replay.invulnerability;
+    }
+
+    let mut app = App::new();
+    if config.headless {
+        // no window, rendering or audio, every frame runs exactly one gameplay tick,
+        // the sprite sheet is still loaded for the mask hitboxes
+        app.add_plugins((
+            MinimalPlugins,
+            LogPlugin::default(),
+            InputPlugin,
+            AssetPlugin::default(),
+            ImagePlugin::default(),
+        ))
+        .init_asset::<TextureAtlas>()
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f64(
+            1. / config.tick_rate,
+        )))
+        .init_resource::<GameTextures>()
+        .add_systems(Last, headless_exit_system)
+        .add_systems(OnEnter(AppState::GameOver), headless_game_over_system);
+    } else {
+        app.add_plugins(DefaultPlugins.set(WindowPlugin {
             primary_window: Some(Window {
                 title: APP_NAME.into(),
                 resolution: WINDOW_SIZE.into(),
@@ -69,37 +178,68 @@ fn main() {
             }),
             ..Default::default()
         }))
-        .add_plugins((PlayerPlugin, EnemyPlugin))
-        .add_plugins(MovementPlugin)
+        .add_plugins((HudPlugin, DebugPlugin))
+        .add_systems(PreStartup, setup_system)
+        .add_systems(Startup, background_spawn_system)
+        .add_systems(PreUpdate, playfield_resize_system);
+    }
+
+    let seed = config.seed.unwrap_or_else(rand::random);
+    if let Some(path) = &config.record {
+        app.insert_resource(ReplayRecorder::new(
+            path.clone(),
+            seed,
+            config.tick_rate,
+            config.invulnerability,
+        ));
+    }
+    if let Some(replay) = playback {
+        app.insert_resource(ReplayPlayback::new(replay));
+    }
+
+    let tick_rate = config.tick_rate;
+    app.insert_resource(config)
+        .insert_resource(PlayField::from(WINDOW_SIZE))
+        .insert_resource(GameRng::new(seed))
+        .insert_resource(Time::<Fixed>::from_hz(tick_rate))
+        .add_systems(Startup, seed_report_system)
+        .add_systems(Update, monitor.run_if(on_timer(Duration::from_secs(2))))
+        .add_plugins(StatePlugin)
+        .add_plugins(DataPlugin)
+        .add_plugins((
+            PlayerPlugin,
+            EnemyPlugin,
+            WavePlugin,
+            BossPlugin,
+            EmitterPlugin,
+            PickupPlugin,
+            MeteorPlugin,
+            ShieldPlugin,
+        ))
+        .add_plugins((MovementPlugin, PathPlugin, PoolPlugin, AnimationPlugin))
         .add_plugins(CollisionPlugin)
+        .add_plugins(ScorePlugin)
+        .add_plugins(HighScorePlugin)
+        .add_plugins(ReplayPlugin)
         .run();
 }
 
+fn exit_with_usage(err: &str) -> ! {
+    eprintln!("{APP_NAME}: {err}");
+    eprintln!(
+        "usage: {APP_NAME} [--headless] [--frames <n>] [--seed <n>] [--record <file>] [--replay <file>] [--tick-rate <hz>] [--invulnerability <seconds>] [--name <name>]"
+    );
+    std::process::exit(2);
+}
+
 fn setup_system(mut commands: Commands, asset_server: Res<AssetServer>) {
     // camera
     commands.spawn(Camera2dBundle::default());
 
-    // add GameTextures resource
+    // add GameTextures resource, the sprite sheet is filled in once loaded
     let game_textures = GameTextures {
         background: asset_server.load(BACKGROUND),
-        player: asset_server.load(PLAYER_SPRITE),
-        player_bucket: PLAYER_SPRITE_BUCKET
-            .into_iter()
-            .map(|path| asset_server.load(path))
-            .collect(),
-        player_laser: asset_server.load(PLAYER_LASER_SPRITE),
-        player_explosion: asset_server.load(PLAYER_EXPLOSION_SPRITE),
-        enemy: asset_server.load(ENEMY_SPRITE),
-        enemy_bucket: ENEMY_SPRITE_BUCKET
-            .into_iter()
-            .map(|path| asset_server.load(path))
-            .collect(),
-        enemy_laser: asset_server.load(ENEMY_LASER_SPRITE),
-        enemy_explosion: asset_server.load(ENEMY_EXPLOSION_SPRITE),
-        explosion_bucket: EXPLOSION_BUCKET
-            .into_iter()
-            .map(|path| asset_server.load(path))
-            .collect(),
+        ..Default::default()
     };
     commands.insert_resource(game_textures);
 }
@@ -107,28 +247,82 @@ fn setup_system(mut commands: Commands, asset_server: Res<AssetServer>) {
 fn background_spawn_system(
     mut commands: Commands,
     game_textures: Res<GameTextures>,
-    win_query: Query<&Window, With<PrimaryWindow>>,
+    playfield: Res<PlayField>,
 ) {
-    // capture window
-    let win = win_query.single();
-    let ww = win.resolution.width();
-    let wh = win.resolution.height();
-
     // add background
-    commands.spawn(SpriteBundle {
-        sprite: Sprite {
-            custom_size: Some(Vec2::new(ww, wh)),
-            ..Default::default()
-        },
-        texture: game_textures.background.clone(),
-        transform: Transform {
-            translation: Vec3::new(0., 0., 0.),
+    commands
+        .spawn(SpriteBundle {
+            sprite: Sprite {
+                custom_size: Some(Vec2::new(playfield.w, playfield.h)),
+                ..Default::default()
+            },
+            texture: game_textures.background.clone(),
+            transform: Transform {
+                translation: Vec3::new(0., 0., 0.),
+                ..Default::default()
+            },
             ..Default::default()
-        },
-        ..Default::default()
-    });
+        })
+        .insert(Background);
+}
+
+fn seed_report_system(rng: Res<GameRng>) {
+    info!(
+        "rng seed: {} (replay with --seed {})",
+        rng.seed(),
+        rng.seed()
+    );
+}
+
+fn playfield_resize_system(
+    mut resize_events: EventReader<WindowResized>,
+    mut playfield: ResMut<PlayField>,
+    mut background_query: Query<&mut Sprite, With<Background>>,
+) {
+    // minimized windows report a zero size, the playfield stays as it was
+    let Some(resized) = resize_events
+        .read()
+        .filter(|resized| resized.width > 0. && resized.height > 0.)
+        .last()
+    else {
+        return;
+    };
+    *playfield = PlayField::from((
+        resized.width.max(PLAYFIELD_MIN_SIZE.0),
+        resized.height.max(PLAYFIELD_MIN_SIZE.1),
+    ));
+    for mut sprite in background_query.iter_mut() {
+        sprite.custom_size = Some(Vec2::new(playfield.w, playfield.h));
+    }
+}
+
+fn headless_exit_system(
+    mut frame: Local<u64>,
+    config: Res<GameConfig>,
+    mut exit: EventWriter<AppExit>,
+) {
+    *frame += 1;
+    if config.frames.is_some_and(|frames| *frame >= frames) {
+        info!("headless run finished after {} frames", *frame);
+        exit.send(AppExit);
+    }
+}
+
+fn headless_game_over_system(player_state: Res<PlayerState>, mut exit: EventWriter<AppExit>) {
+    info!(
+        "headless run finished at game over, score {}",
+        player_state.score
+    );
+    exit.send(AppExit);
 }
 
 pub fn monitor(world: &mut World) {
     info!("entities entities {:?}", world.entities().total_count());
+    let pool = world.resource::<SpritePool>();
+    info!(
+        "sprite pool: {} hits, {} misses, {} free",
+        pool.hits,
+        pool.misses,
+        pool.free.len()
+    );
 }
@@ -0,0 +1,27 @@
+use crate::{component::animation::Animation, plugin::state::GameplaySet, resource::pool::Release};
+use bevy::prelude::*;
+
+pub struct AnimationPlugin;
+
+impl Plugin for AnimationPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(FixedUpdate, animation_system.in_set(GameplaySet));
+    }
+}
+
+/// Show the current frame of every animation, finished ones are released if asked to
+pub fn animation_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut Animation, &mut TextureAtlasSprite)>,
+) {
+    for (entity, mut animation, mut sprite) in query.iter_mut() {
+        animation.elapsed += time.delta_seconds();
+        match animation.frame() {
+            Some(index) if sprite.index != index => sprite.index = index,
+            Some(_) => {}
+            None if animation.despawn => commands.add(Release(entity)),
+            None => {}
+        }
+    }
+}
@@ -0,0 +1,421 @@
+use crate::{
+    asset::{
+        boss::{BossLibrary, BossMovement, BossPhase},
+        path::PathLibrary,
+    },
+    component::{
+        boss::{Boss, BossBeam, BossBeamSegment},
+        collider::{Collider, Layers},
+        damage::{Damage, HitFlash},
+        emitter::BulletEmitter,
+        enemy::{Enemy, EnemyStats, FromEnemy},
+        explosion::ExplosionKind,
+        fomation::{Formation, FormationMotion},
+        health::Health,
+        laser::Laser,
+        path::{PathFollower, SampledPath},
+        state::InGame,
+    },
+    event::{
+        boss::{BossDefeated, BossPhaseChanged, BossSpawned},
+        wave::WaveStarted,
+    },
+    plugin::{
+        collision::{enemy_laser_collision_system, spawn_explosion},
+        enemy::enemy_move_system,
+        state::GameplaySet,
+        wave::wave_director_system,
+    },
+    resource::{
+        config::windowed, data::GameData, global::GameTextures, playfield::PlayField,
+        pool::SpritePool, wave::WaveDirector,
+    },
+    BOSS_BEAM_DAMAGE, BOSS_BEAM_SEGMENT_LENGTH, BOSS_BEAM_SIZE, SPRITE_SCALE,
+};
+use bevy::prelude::*;
+use std::{
+    f32::consts::{PI, TAU},
+    sync::Arc,
+};
+
+pub struct BossPlugin;
+
+impl Plugin for BossPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<BossSpawned>()
+            .add_event::<BossPhaseChanged>()
+            .add_event::<BossDefeated>()
+            .add_systems(Update, boss_audio_system.run_if(windowed))
+            .add_systems(
+                FixedUpdate,
+                (
+                    boss_spawn_system
+                        .after(wave_director_system)
+                        .run_if(on_event::<WaveStarted>()),
+                    boss_phase_system
+                        .after(enemy_laser_collision_system)
+                        .after(enemy_move_system),
+                    boss_attack_system.after(boss_phase_system),
+                    boss_beam_system.after(boss_attack_system),
+                    boss_effect_system,
+                )
+                    .in_set(GameplaySet),
+            );
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn boss_spawn_system(
+    mut commands: Commands,
+    game_textures: Res<GameTextures>,
+    game_data: Res<GameData>,
+    boss_libraries: Res<Assets<BossLibrary>>,
+    path_libraries: Res<Assets<PathLibrary>>,
+    wave_director: Res<WaveDirector>,
+    playfield: Res<PlayField>,
+    mut spawned_events: EventWriter<BossSpawned>,
+) {
+    let Some(name) = wave_director.def.as_ref().and_then(|def| def.boss.as_ref()) else {
+        return;
+    };
+    let (Some(bosses), Some(paths)) = (
+        boss_libraries.get(&game_data.bosses),
+        path_libraries.get(&game_data.paths),
+    ) else {
+        return;
+    };
+    let Some(def) = bosses.get(name) else {
+        return;
+    };
+
+    // enter from the top
+    let start = Vec2::new(0., playfield.h / 2. + 150.);
+    let mut boss = Boss::new(def);
+    let (motion, pending) = phase_motion(boss.current(), start, paths, &playfield);
+    boss.pending = pending;
+    let scale = SPRITE_SCALE * def.scale;
+    let sprite = game_textures
+        .bosses
+        .get(&def.name)
+        .copied()
+        .unwrap_or_default();
+    commands
+        .spawn(SpriteSheetBundle {
+            sprite: TextureAtlasSprite::new(sprite),
+            texture_atlas: game_textures.sheet.clone(),
+            transform: Transform {
+                translation: start.extend(10.),
+                scale: Vec3::new(scale, scale, 1.),
+                rotation: Quat::from_rotation_x(PI),
+            },
+            ..Default::default()
+        })
+        .insert(Enemy)
+        .insert(InGame)
+        .insert(def.sprite_size())
+        .insert(game_textures.hitbox(&def.hitbox, sprite))
+        .insert(Collider::new(Layers::ENEMY, Layers::PLAYER_PROJECTILE))
+        .insert(Health::new(def.hp))
+        .insert(HitFlash::default())
+        .insert(EnemyStats { score: def.score })
+        .insert(BulletEmitter::new(boss.current().patterns.clone()))
+        .insert(Formation {
+            start: (start.x, start.y),
+            speed: boss.current().speed,
+            elapsed: 0.,
+            motion,
+        })
+        .insert(boss);
+    info!("boss {name} spawned");
+    spawned_events.send(BossSpawned { name: name.clone() });
+}
+
+/// Start the next phase at its health threshold, and remove the defeated boss
+#[allow(clippy::type_complexity)]
+pub fn boss_phase_system(
+    mut commands: Commands,
+    game_data: Res<GameData>,
+    path_libraries: Res<Assets<PathLibrary>>,
+    playfield: Res<PlayField>,
+    mut phase_events: EventWriter<BossPhaseChanged>,
+    mut defeated_events: EventWriter<BossDefeated>,
+    mut query: Query<(
+        Entity,
+        &Transform,
+        &Health,
+        &mut Boss,
+        &mut Formation,
+        &mut BulletEmitter,
+    )>,
+) {
+    let Some(paths) = path_libraries.get(&game_data.paths) else {
+        return;
+    };
+    for (entity, transform, health, mut boss, mut formation, mut emitter) in query.iter_mut() {
+        let position = transform.translation;
+        // the kill itself was scored by the laser collision
+        if health.hp == 0 {
+            info!("boss {} defeated", boss.name);
+            defeated_events.send(BossDefeated { position });
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        // follow the path once its start is reached
+        if let FormationMotion::Hold { slot, .. } = formation.motion {
+            if boss.pending.is_some() && position.truncate().distance(slot) < 1. {
+                formation.motion = boss.pending.take().unwrap_or(formation.motion.clone());
+                formation.elapsed = 0.;
+            }
+        }
+
+        let phase = boss.phase_for(health.hp as f32 / health.max as f32);
+        if phase == boss.phase {
+            continue;
+        }
+        boss.enter_phase(phase);
+        *emitter = BulletEmitter::new(boss.current().patterns.clone());
+        let (motion, pending) =
+            phase_motion(boss.current(), position.truncate(), paths, &playfield);
+        *formation = Formation {
+            start: (position.x, position.y),
+            speed: boss.current().speed,
+            elapsed: 0.,
+            motion,
+        };
+        boss.pending = pending;
+        info!("boss {} phase {}", boss.name, phase + 1);
+        phase_events.send(BossPhaseChanged { phase, position });
+    }
+}
+
+/// Movement of `phase` for a boss at `position`, a path first flies to its start
+fn phase_motion(
+    phase: &BossPhase,
+    position: Vec2,
+    paths: &PathLibrary,
+    playfield: &PlayField,
+) -> (FormationMotion, Option<FormationMotion>) {
+    let top = Vec2::new(0., playfield.h / 4. + 30.);
+    match &phase.movement {
+        BossMovement::Hold { sway } => (
+            FormationMotion::Hold {
+                slot: top,
+                sway: *sway,
+            },
+            None,
+        ),
+        BossMovement::Orbit { radius } => (
+            FormationMotion::Orbit {
+                radius: *radius,
+                pivot: (top.x, top.y),
+                angle: (position.y - top.y).atan2(position.x - top.x),
+            },
+            None,
+        ),
+        BossMovement::Path { path, mode } => {
+            let Some(def) = paths.get(path) else {
+                return (
+                    FormationMotion::Hold {
+                        slot: top,
+                        sway: 0.,
+                    },
+                    None,
+                );
+            };
+            let path = SampledPath::from_def(def, Vec2::new(playfield.w / 2., playfield.h / 2.));
+            let slot = path.start();
+            let follower = PathFollower::new(Arc::new(path), phase.speed, *mode);
+            (
+                FormationMotion::Hold { slot, sway: 0. },
+                Some(FormationMotion::Path(follower)),
+            )
+        }
+    }
+}
+
+/// Start the beams of the current phase as their cooldowns run out, bullets are left
+/// to the boss emitter
+pub fn boss_attack_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    game_textures: Res<GameTextures>,
+    playfield: Res<PlayField>,
+    beam_query: Query<&BossBeam>,
+    mut boss_query: Query<(Entity, &Transform, &mut Boss)>,
+) {
+    let time_step = time.delta_seconds();
+    for (entity, transform, mut boss) in boss_query.iter_mut() {
+        // hold fire until the boss is on screen
+        if !playfield.contains(transform.translation, 0.) {
+            continue;
+        }
+        let origin = transform.translation.truncate();
+        let Boss {
+            phases,
+            phase,
+            cooldowns,
+            ..
+        } = &mut *boss;
+        for (beam, cooldown) in phases[*phase].beams.iter().zip(cooldowns.iter_mut()) {
+            *cooldown -= time_step;
+            if *cooldown > 0. {
+                continue;
+            }
+            *cooldown += beam.interval;
+            // one beam at a time
+            if beam_query.iter().any(|beam| beam.boss == entity) {
+                continue;
+            }
+            let segments = (0..beam.segments)
+                .map(|_| {
+                    commands
+                        .spawn(SpriteSheetBundle {
+                            sprite: TextureAtlasSprite {
+                                index: game_textures.boss_beam,
+                                color: Color::WHITE.with_a(0.35),
+                                ..Default::default()
+                            },
+                            texture_atlas: game_textures.sheet.clone(),
+                            transform: Transform {
+                                translation: origin.extend(9.),
+                                scale: Vec3::new(SPRITE_SCALE, SPRITE_SCALE, 1.),
+                                ..Default::default()
+                            },
+                            ..Default::default()
+                        })
+                        .insert(BossBeamSegment::default())
+                        .insert(InGame)
+                        .id()
+                })
+                .collect();
+            commands
+                .spawn(SpriteSheetBundle {
+                    sprite: TextureAtlasSprite::new(game_textures.boss_beam_muzzle),
+                    texture_atlas: game_textures.sheet.clone(),
+                    transform: Transform {
+                        translation: origin.extend(11.),
+                        scale: Vec3::new(SPRITE_SCALE, SPRITE_SCALE, 1.),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                })
+                .insert(BossBeam {
+                    boss: entity,
+                    segments,
+                    sweep: beam.sweep,
+                    warmup: beam.warmup,
+                    duration: beam.duration,
+                    elapsed: 0.,
+                    active: false,
+                })
+                .insert(InGame);
+        }
+    }
+}
+
+/// Sweep the beams below their boss, they only hurt once warmed up
+#[allow(clippy::type_complexity)]
+pub fn boss_beam_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    boss_query: Query<&Transform, (With<Boss>, Without<BossBeam>, Without<BossBeamSegment>)>,
+    mut beam_query: Query<(Entity, &mut Transform, &mut BossBeam), Without<BossBeamSegment>>,
+    mut segment_query: Query<(&mut Transform, &mut TextureAtlasSprite), With<BossBeamSegment>>,
+) {
+    for (entity, mut transform, mut beam) in beam_query.iter_mut() {
+        beam.elapsed += time.delta_seconds();
+        let boss = boss_query.get(beam.boss);
+        if boss.is_err() || beam.elapsed >= beam.warmup + beam.duration {
+            for segment in beam.segments.iter() {
+                if let Some(mut segment) = commands.get_entity(*segment) {
+                    segment.despawn();
+                }
+            }
+            commands.entity(entity).despawn();
+            continue;
+        }
+        let Ok(boss_tf) = boss else {
+            continue;
+        };
+
+        // hold the first edge of the sweep while warming up, 0 points straight down
+        let progress = ((beam.elapsed - beam.warmup) / beam.duration).clamp(0., 1.);
+        let angle = (beam.sweep * (progress - 0.5)).to_radians();
+        let dir = Vec2::new(angle.sin(), -angle.cos());
+        let origin = boss_tf.translation.truncate() + dir * 30.;
+        transform.translation = origin.extend(11.);
+
+        let activate = !beam.active && beam.elapsed >= beam.warmup;
+        beam.active |= activate;
+        for (index, segment) in beam.segments.iter().enumerate() {
+            let Ok((mut segment_tf, mut sprite)) = segment_query.get_mut(*segment) else {
+                continue;
+            };
+            let offset = BOSS_BEAM_SEGMENT_LENGTH * (index as f32 + 0.5);
+            segment_tf.translation = (origin + dir * offset).extend(9.);
+            segment_tf.rotation = Quat::from_rotation_z(angle);
+            if activate {
+                sprite.color.set_a(1.);
+                commands
+                    .entity(*segment)
+                    .insert(Laser)
+                    .insert(FromEnemy)
+                    .insert(BOSS_BEAM_SIZE)
+                    .insert(Collider::passive(Layers::ENEMY_PROJECTILE))
+                    .insert(Damage(BOSS_BEAM_DAMAGE));
+            }
+        }
+    }
+}
+
+/// Ring of explosions when a boss changes phase or is destroyed
+pub fn boss_effect_system(
+    mut commands: Commands,
+    game_textures: Res<GameTextures>,
+    mut pool: ResMut<SpritePool>,
+    mut phase_events: EventReader<BossPhaseChanged>,
+    mut defeated_events: EventReader<BossDefeated>,
+) {
+    let bursts = phase_events
+        .read()
+        .map(|phase_changed| (phase_changed.position, 6, 40.))
+        .chain(
+            defeated_events
+                .read()
+                .map(|defeated| (defeated.position, 12, 60.)),
+        );
+    for (position, count, radius) in bursts {
+        for index in 0..count {
+            let offset = Vec2::from_angle(TAU * index as f32 / count as f32) * radius;
+            spawn_explosion(
+                &mut commands,
+                &mut pool,
+                &game_textures,
+                position + offset.extend(1.),
+                ExplosionKind::Enemy,
+                2.,
+            );
+        }
+    }
+}
+
+pub fn boss_audio_system(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut spawned_events: EventReader<BossSpawned>,
+    mut phase_events: EventReader<BossPhaseChanged>,
+    mut defeated_events: EventReader<BossDefeated>,
+) {
+    let sounds = spawned_events
+        .read()
+        .map(|_| "Bonus/sfx_twoTone.ogg")
+        .chain(phase_events.read().map(|_| "Bonus/sfx_zap.ogg"))
+        .chain(defeated_events.read().map(|_| "Bonus/sfx_twoTone.ogg"));
+    for sound in sounds {
+        commands.spawn(AudioBundle {
+            source: asset_server.load(sound),
+            settings: PlaybackSettings::DESPAWN,
+        });
+    }
+}
@@ -1,167 +1,341 @@
 use crate::{
+    asset::hitbox::HitboxShape,
     component::{
-        enemy::{Enemy, FromEnemy},
-        explosion::{Explosion, ExplosionTimer},
+        animation::{Animation, AnimationMode},
+        boss::{Boss, BossBeamSegment},
+        collider::Collider,
+        damage::{Damage, HitFlash},
+        enemy::{Enemy, EnemyStats, FromEnemy},
+        explosion::{Explosion, ExplosionKind},
+        health::Health,
+        hitbox::{Hitbox, WorldShape},
         laser::Laser,
-        player::{FromPlayer, Player},
+        meteor::Meteor,
+        player::{FromPlayer, Invulnerable, Player},
+        shield::Shield,
         sprite::SpriteSize,
+        state::InGame,
+        weapon::Piercing,
     },
-    resource::global::{GameTextures, PlayerState},
-    ENEMY_EXPLOSION_SIZE, PLAYER_EXPLOSION_SIZE, SPRITE_SCALE,
+    event::{
+        collision::Collision,
+        player::{
+            ComboChanged, EnemyKilled, GameOver, LivesChanged, PlayerKilled, ScoreChanged,
+            ShieldDown,
+        },
+    },
+    plugin::{
+        emitter::homing_system, enemy::enemy_move_system, movement::movement_system,
+        path::path_follow_system, state::GameplaySet,
+    },
+    resource::{
+        broadphase::{SpatialEntry, SpatialHash},
+        global::{GameTextures, PlayerState},
+        pickup::PowerUps,
+        pool::{Release, SpritePool},
+    },
+    COLLISION_CELL_SIZE, EXPLOSION_FPS, HIT_FLASH_COLOR, SPRITE_SCALE,
 };
-use bevy::{prelude::*, sprite::collide_aabb::collide};
-use std::collections::HashSet;
+use bevy::{ecs::query::Has, prelude::*, utils::HashSet};
 
 pub struct CollisionPlugin;
 
 impl Plugin for CollisionPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, enemy_laser_collision_system)
-            .add_systems(Update, plaer_laser_collision_system)
-            .add_systems(Update, enemy_explosion_animation_system);
+        app.add_event::<Collision>()
+            .insert_resource(SpatialHash::new(COLLISION_CELL_SIZE))
+            .add_systems(
+                FixedUpdate,
+                (
+                    // overlaps of where everything ended up this tick
+                    collision_detection_system
+                        .after(movement_system)
+                        .after(path_follow_system)
+                        .after(enemy_move_system)
+                        .after(homing_system),
+                    (enemy_laser_collision_system, plaer_laser_collision_system)
+                        .after(collision_detection_system),
+                    hit_flash_system,
+                )
+                    .in_set(GameplaySet),
+            );
+    }
+}
+
+/// Rebuild the spatial hash, then send a `Collision` for every overlap a collider mask asks for
+pub fn collision_detection_system(
+    mut spatial_hash: ResMut<SpatialHash>,
+    mut collision_events: EventWriter<Collision>,
+    query: Query<(Entity, &Transform, &SpriteSize, &Collider, Option<&Hitbox>)>,
+) {
+    spatial_hash.clear();
+    for (entity, transform, size, collider, hitbox) in query.iter() {
+        let shape = WorldShape::new(
+            hitbox.map_or(&HitboxShape::Rect, |hitbox| &hitbox.0),
+            size,
+            transform,
+        );
+        let (min, max) = shape.bounds();
+        spatial_hash.insert(SpatialEntry {
+            entity,
+            shape,
+            min,
+            max,
+            layer: collider.layer,
+            mask: collider.mask,
+        });
+    }
+    // projectiles have no mask, only the few colliders looking for them query the grid
+    for entry in spatial_hash.entries.iter() {
+        if entry.mask == Default::default() {
+            continue;
+        }
+        collision_events.send_batch(spatial_hash.overlaps(entry).map(|other| Collision {
+            entity: entry.entity,
+            other: other.entity,
+        }));
     }
 }
 
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
 pub fn enemy_laser_collision_system(
     mut commands: Commands,
+    time: Res<Time>,
+    mut player_state: ResMut<PlayerState>,
+    power_ups: Res<PowerUps>,
     game_textures: Res<GameTextures>,
-    enemy_query: Query<(Entity, &Transform, &SpriteSize), With<Enemy>>,
-    laser_query: Query<(Entity, &Transform, &SpriteSize), (With<Laser>, With<FromPlayer>)>,
+    mut pool: ResMut<SpritePool>,
+    mut collision_events: EventReader<Collision>,
+    mut kill_events: EventWriter<EnemyKilled>,
+    mut score_events: EventWriter<ScoreChanged>,
+    mut combo_events: EventWriter<ComboChanged>,
+    mut enemy_query: Query<
+        (
+            &Transform,
+            &mut Health,
+            &mut HitFlash,
+            &EnemyStats,
+            Has<Boss>,
+        ),
+        With<Enemy>,
+    >,
+    mut laser_query: Query<(&Damage, Option<&mut Piercing>), (With<Laser>, With<FromPlayer>)>,
 ) {
     let mut despawn_set = HashSet::<Entity>::new();
-    for (enemy_entity, enemy_tf, enemy_size) in enemy_query.iter() {
-        let enemy_scale = Vec2::new(enemy_tf.scale.x, enemy_tf.scale.y);
-        for (laser_entity, laser_tf, laser_size) in laser_query.iter() {
-            let laser_scale = Vec2::new(laser_tf.scale.x, laser_tf.scale.y);
-            let collision = collide(
-                enemy_tf.translation,
-                Vec2 {
-                    x: enemy_size.w,
-                    y: enemy_size.h,
-                } * enemy_scale,
-                laser_tf.translation,
-                Vec2 {
-                    x: laser_size.w,
-                    y: laser_size.h,
-                } * laser_scale,
-            );
-            if let Some(_) = collision {
-                despawn_set.insert(enemy_entity);
-                despawn_set.insert(laser_entity);
-                // spawn explosion
-                commands
-                    .spawn(SpriteBundle {
-                        texture: game_textures.enemy_explosion.clone(),
-                        transform: Transform {
-                            translation: enemy_tf.translation,
-                            scale: Vec3::new(SPRITE_SCALE, SPRITE_SCALE, 1.),
-                            ..Default::default()
-                        },
-                        ..Default::default()
-                    })
-                    .insert(Explosion)
-                    .insert(ExplosionTimer::default())
-                    .insert(ENEMY_EXPLOSION_SIZE);
+    for collision in collision_events.read() {
+        let (Ok((enemy_tf, mut health, mut flash, enemy_stats, boss)), Ok((damage, piercing))) = (
+            enemy_query.get_mut(collision.entity),
+            laser_query.get_mut(collision.other),
+        ) else {
+            continue;
+        };
+        // a laser only hits once, and a destroyed enemy takes no more hits
+        if despawn_set.contains(&collision.other) || health.hp == 0 {
+            continue;
+        }
+        // a piercing laser flies on, hitting every enemy once
+        match piercing {
+            Some(piercing) if piercing.hit.contains(&collision.entity) => continue,
+            Some(mut piercing) => piercing.hit.push(collision.entity),
+            None => {
+                despawn_set.insert(collision.other);
             }
         }
+        // tougher archetypes take several hits
+        flash.hit();
+        if !health.damage(damage.0) {
+            continue;
+        }
+        // a boss stays around for its defeat effects
+        if !boss {
+            despawn_set.insert(collision.entity);
+        }
+        // award the kill
+        let combo = player_state.combo;
+        let points = player_state.kill(
+            enemy_stats.score * power_ups.score_multiplier(),
+            time.elapsed_seconds_f64(),
+        );
+        kill_events.send(EnemyKilled {
+            position: enemy_tf.translation,
+            points,
+        });
+        score_events.send(ScoreChanged {
+            score: player_state.score,
+            points,
+        });
+        if player_state.combo != combo {
+            combo_events.send(ComboChanged {
+                combo: player_state.combo,
+            });
+        }
+        spawn_explosion(
+            &mut commands,
+            &mut pool,
+            &game_textures,
+            enemy_tf.translation,
+            ExplosionKind::Enemy,
+            1.,
+        );
     }
     despawn_set
         .iter()
-        .for_each(move |el| commands.entity(*el).despawn());
+        .for_each(move |el| commands.add(Release(*el)));
 }
 
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
 pub fn plaer_laser_collision_system(
     mut commands: Commands,
     time: Res<Time>,
     mut player_state: ResMut<PlayerState>,
     game_textures: Res<GameTextures>,
-    player_query: Query<(Entity, &Transform, &SpriteSize), With<Player>>,
-    laser_query: Query<(Entity, &Transform, &SpriteSize), (With<Laser>, With<FromEnemy>)>,
+    mut pool: ResMut<SpritePool>,
+    mut collision_events: EventReader<Collision>,
+    mut shield_events: EventWriter<ShieldDown>,
+    mut killed_events: EventWriter<PlayerKilled>,
+    mut lives_events: EventWriter<LivesChanged>,
+    mut combo_events: EventWriter<ComboChanged>,
+    mut game_over_events: EventWriter<GameOver>,
+    mut player_query: Query<
+        (
+            &Transform,
+            &mut Health,
+            &mut HitFlash,
+            Option<&mut Shield>,
+            Has<Invulnerable>,
+        ),
+        With<Player>,
+    >,
+    mut laser_query: Query<
+        (&Damage, Option<&mut BossBeamSegment>, Has<Meteor>),
+        Or<((With<Laser>, With<FromEnemy>), With<Meteor>)>,
+    >,
 ) {
     let mut despawn_set = HashSet::<Entity>::new();
-    for (player_entity, player_tf, player_size) in player_query.iter() {
-        let player_scale = Vec2::new(player_tf.scale.x, player_tf.scale.y);
-        for (laser_entity, laser_tf, laser_size) in laser_query.iter() {
-            let laser_scale = Vec2::new(laser_tf.scale.x, laser_tf.scale.y);
-            let collision = collide(
-                player_tf.translation,
-                Vec2 {
-                    x: player_size.w,
-                    y: player_size.h,
-                } * player_scale,
-                laser_tf.translation,
-                Vec2 {
-                    x: laser_size.w,
-                    y: laser_size.h,
-                } * laser_scale,
-            );
-            if let Some(_) = collision {
-                despawn_set.insert(player_entity);
-                despawn_set.insert(laser_entity);
-                player_state.shot(time.elapsed_seconds_f64());
-                // spawn explosion
-                commands
-                    .spawn(SpriteBundle {
-                        texture: game_textures.enemy_explosion.clone(),
-                        transform: Transform {
-                            translation: player_tf.translation,
-                            scale: Vec3::new(SPRITE_SCALE, SPRITE_SCALE, 1.),
-                            ..Default::default()
-                        },
-                        ..Default::default()
-                    })
-                    .insert(Explosion)
-                    .insert(ExplosionTimer::default())
-                    .insert(PLAYER_EXPLOSION_SIZE);
+    for collision in collision_events.read() {
+        let (
+            Ok((player_tf, mut health, mut flash, shield, invulnerable)),
+            Ok((damage, segment, meteor)),
+        ) = (
+            player_query.get_mut(collision.entity),
+            laser_query.get_mut(collision.other),
+        )
+        else {
+            continue;
+        };
+        if despawn_set.contains(&collision.other) || health.hp == 0 {
+            continue;
+        }
+        // bullets and meteors go through a respawned ship
+        if invulnerable {
+            continue;
+        }
+        // beam segments stay, the beam keeps no holes, and meteors break up on the ship,
+        // see `meteor_hit_system`
+        match segment {
+            Some(segment) if segment.hit.contains(&collision.entity) => continue,
+            Some(mut segment) => segment.hit.push(collision.entity),
+            None if !meteor => {
+                despawn_set.insert(collision.other);
             }
+            None => {}
+        }
+        // the shield absorbs the bullet or meteor
+        if let Some(mut shield) = shield {
+            if shield.absorb() {
+                if shield.hits == 0 {
+                    shield_events.send(ShieldDown);
+                }
+                continue;
+            }
+        }
+        flash.hit();
+        if !health.damage(damage.0) {
+            continue;
+        }
+        despawn_set.insert(collision.entity);
+        let combo = player_state.combo;
+        player_state.shot(time.elapsed_seconds_f64());
+        killed_events.send(PlayerKilled {
+            position: player_tf.translation,
+        });
+        lives_events.send(LivesChanged {
+            lives: player_state.lives,
+        });
+        if player_state.combo != combo {
+            combo_events.send(ComboChanged {
+                combo: player_state.combo,
+            });
+        }
+        if player_state.game_over() {
+            game_over_events.send(GameOver {
+                score: player_state.score,
+            });
         }
+        spawn_explosion(
+            &mut commands,
+            &mut pool,
+            &game_textures,
+            player_tf.translation,
+            ExplosionKind::Player,
+            1.5,
+        );
     }
     despawn_set
         .iter()
-        .for_each(move |el| commands.entity(*el).despawn());
+        .for_each(move |el| commands.add(Release(*el)));
 }
 
-pub fn enemy_explosion_animation_system(
-    mut commands: Commands,
-    game_textures: Res<GameTextures>,
+/// Explosion of `kind` playing its sheet frames once, `scale` times their size
+pub fn spawn_explosion(
+    commands: &mut Commands,
+    pool: &mut SpritePool,
+    game_textures: &GameTextures,
+    position: Vec3,
+    kind: ExplosionKind,
+    scale: f32,
+) {
+    let frames = game_textures
+        .explosions
+        .get(&kind)
+        .cloned()
+        .unwrap_or_default();
+    pool.acquire(commands)
+        .insert(SpriteSheetBundle {
+            sprite: TextureAtlasSprite::new(frames.first().copied().unwrap_or_default()),
+            texture_atlas: game_textures.sheet.clone(),
+            transform: Transform {
+                translation: position,
+                scale: Vec3::new(SPRITE_SCALE * scale, SPRITE_SCALE * scale, 1.),
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .insert(Explosion)
+        .insert(InGame)
+        .insert(Animation::new(frames, EXPLOSION_FPS, AnimationMode::Once).despawn_on_finish());
+}
+
+/// Tint what was just hit, back to its own colors once the flash is over
+pub fn hit_flash_system(
     time: Res<Time>,
-    mut explosion_query: Query<
-        (Entity, &Transform, &SpriteSize, &mut ExplosionTimer),
-        With<Explosion>,
-    >,
+    mut query: Query<(AnyOf<(&mut Sprite, &mut TextureAtlasSprite)>, &mut HitFlash)>,
 ) {
-    for (explosion_entity, explosion_tf, explosion_size, mut explosion_timer) in
-        explosion_query.iter_mut()
-    {
-        explosion_timer.0.tick(time.delta());
-        if explosion_timer.0.finished() {
-            commands.entity(explosion_entity).despawn();
-            let next_explosion_size = SpriteSize {
-                w: explosion_size.w - 5.,
-                h: explosion_size.h - 5.,
-            };
-            if next_explosion_size.w > 5. && next_explosion_size.h > 5. {
-                commands
-                    .spawn(SpriteBundle {
-                        sprite: Sprite {
-                            custom_size: Some(Vec2 {
-                                x: next_explosion_size.w,
-                                y: next_explosion_size.h,
-                            }),
-                            ..Default::default()
-                        },
-                        texture: game_textures.enemy_explosion.clone(),
-                        transform: Transform {
-                            translation: explosion_tf.translation,
-                            scale: Vec3::new(SPRITE_SCALE, SPRITE_SCALE, 1.),
-                            ..Default::default()
-                        },
-                        ..Default::default()
-                    })
-                    .insert(Explosion)
-                    .insert(ExplosionTimer::default())
-                    .insert(next_explosion_size);
-            }
+    for ((sprite, sheet_sprite), mut flash) in query.iter_mut() {
+        if flash.remaining <= 0. {
+            continue;
+        }
+        flash.remaining -= time.delta_seconds();
+        let color = if flash.remaining > 0. {
+            HIT_FLASH_COLOR
+        } else {
+            Color::WHITE
+        };
+        if let Some(mut sprite) = sprite {
+            sprite.color = color;
+        }
+        if let Some(mut sprite) = sheet_sprite {
+            sprite.color = color;
         }
     }
 }
@@ -0,0 +1,351 @@
+use crate::{
+    asset::{
+        boss::BossLibrary,
+        enemy::EnemyArchetypes,
+        hitbox::HitboxShape,
+        loader::RonAssetLoader,
+        meteor::MeteorField,
+        path::PathLibrary,
+        pickup::LootTable,
+        sheet::{SpriteSheet, SpriteSheetLoader},
+        wave::WaveScript,
+        weapon::WeaponLibrary,
+    },
+    component::{explosion::ExplosionKind, hitbox::convex_hull},
+    plugin::state::AppState,
+    resource::{data::GameData, global::GameTextures},
+    BOSS_BEAM_MUZZLE_SPRITE, BOSS_BEAM_SPRITE, ENEMY_EXPLOSION_FRAMES, ENEMY_LASER_SPRITE,
+    ENEMY_ORB_SPRITE, METEOR_EXPLOSION_FRAMES, PLAYER_DAMAGE_SPRITES, PLAYER_ENGINE_FRAMES,
+    PLAYER_EXPLOSION_FRAMES, PLAYER_HITBOX, PLAYER_SHIELD_SPRITES, PLAYER_SPRITE,
+};
+use bevy::{app::AppExit, asset::LoadState, prelude::*, utils::HashMap};
+
+pub const ENEMY_ARCHETYPES: &str = "data/default.enemies.ron";
+pub const WAVE_SCRIPT: &str = "data/default.waves.ron";
+pub const PATH_LIBRARY: &str = "data/default.paths.ron";
+pub const BOSS_LIBRARY: &str = "data/default.bosses.ron";
+pub const LOOT_TABLE: &str = "data/default.pickups.ron";
+pub const WEAPON_LIBRARY: &str = "data/default.weapons.ron";
+pub const METEOR_FIELD: &str = "data/default.meteors.ron";
+pub const SPRITE_SHEET: &str = "Spritesheet/sheet.xml";
+
+pub struct DataPlugin;
+
+impl Plugin for DataPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<EnemyArchetypes>()
+            .init_asset::<WaveScript>()
+            .init_asset::<PathLibrary>()
+            .init_asset::<BossLibrary>()
+            .init_asset::<LootTable>()
+            .init_asset::<WeaponLibrary>()
+            .init_asset::<MeteorField>()
+            .init_asset::<SpriteSheet>()
+            .register_asset_loader(RonAssetLoader::<EnemyArchetypes>::new(&["enemies.ron"]))
+            .register_asset_loader(RonAssetLoader::<WaveScript>::new(&["waves.ron"]))
+            .register_asset_loader(RonAssetLoader::<PathLibrary>::new(&["paths.ron"]))
+            .register_asset_loader(RonAssetLoader::<BossLibrary>::new(&["bosses.ron"]))
+            .register_asset_loader(RonAssetLoader::<LootTable>::new(&["pickups.ron"]))
+            .register_asset_loader(RonAssetLoader::<WeaponLibrary>::new(&["weapons.ron"]))
+            .register_asset_loader(RonAssetLoader::<MeteorField>::new(&["meteors.ron"]))
+            .register_asset_loader(SpriteSheetLoader)
+            .add_systems(PreStartup, data_load_system)
+            .add_systems(
+                Update,
+                data_loading_system.run_if(in_state(AppState::Loading)),
+            );
+    }
+}
+
+pub fn data_load_system(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(GameData {
+        enemies: asset_server.load(ENEMY_ARCHETYPES),
+        waves: asset_server.load(WAVE_SCRIPT),
+        paths: asset_server.load(PATH_LIBRARY),
+        bosses: asset_server.load(BOSS_LIBRARY),
+        pickups: asset_server.load(LOOT_TABLE),
+        weapons: asset_server.load(WEAPON_LIBRARY),
+        meteors: asset_server.load(METEOR_FIELD),
+        sheet: asset_server.load(SPRITE_SHEET),
+    });
+}
+
+/// Wait for every data asset, then look the sprites they name up, hull their masks and leave
+/// `AppState::Loading`
+#[allow(clippy::too_many_arguments)]
+pub fn data_loading_system(
+    asset_server: Res<AssetServer>,
+    game_data: Res<GameData>,
+    enemy_archetypes: Res<Assets<EnemyArchetypes>>,
+    wave_scripts: Res<Assets<WaveScript>>,
+    path_libraries: Res<Assets<PathLibrary>>,
+    boss_libraries: Res<Assets<BossLibrary>>,
+    loot_tables: Res<Assets<LootTable>>,
+    weapon_libraries: Res<Assets<WeaponLibrary>>,
+    meteor_fields: Res<Assets<MeteorField>>,
+    sprite_sheets: Res<Assets<SpriteSheet>>,
+    texture_atlases: Res<Assets<TextureAtlas>>,
+    images: Res<Assets<Image>>,
+    mut game_textures: ResMut<GameTextures>,
+    mut next_state: ResMut<NextState<AppState>>,
+    mut exit: EventWriter<AppExit>,
+) {
+    let mut loaded = true;
+    for handle in game_data.handles() {
+        match asset_server.get_load_state(handle.id()) {
+            Some(LoadState::Loaded) => {}
+            Some(LoadState::Failed) => {
+                error!("cannot load {:?}, see the asset error above", handle.path());
+                exit.send(AppExit);
+                return;
+            }
+            _ => loaded = false,
+        }
+    }
+    if !loaded {
+        return;
+    }
+    if let (
+        Some(enemies),
+        Some(waves),
+        Some(paths),
+        Some(bosses),
+        Some(pickups),
+        Some(weapons),
+        Some(meteors),
+    ) = (
+        enemy_archetypes.get(&game_data.enemies),
+        wave_scripts.get(&game_data.waves),
+        path_libraries.get(&game_data.paths),
+        boss_libraries.get(&game_data.bosses),
+        loot_tables.get(&game_data.pickups),
+        weapon_libraries.get(&game_data.weapons),
+        meteor_fields.get(&game_data.meteors),
+    ) {
+        let validation = enemies
+            .validate()
+            .map_err(|err| format!("{ENEMY_ARCHETYPES}: {err}"))
+            .and_then(|_| {
+                paths
+                    .validate()
+                    .map_err(|err| format!("{PATH_LIBRARY}: {err}"))
+            })
+            .and_then(|_| {
+                bosses
+                    .validate(paths)
+                    .map_err(|err| format!("{BOSS_LIBRARY}: {err}"))
+            })
+            .and_then(|_| {
+                waves
+                    .validate(enemies, paths, bosses)
+                    .map_err(|err| format!("{WAVE_SCRIPT}: {err}"))
+            })
+            .and_then(|_| {
+                weapons
+                    .validate()
+                    .map_err(|err| format!("{WEAPON_LIBRARY}: {err}"))
+            })
+            .and_then(|_| {
+                pickups
+                    .validate(weapons)
+                    .map_err(|err| format!("{LOOT_TABLE}: {err}"))
+            })
+            .and_then(|_| {
+                meteors
+                    .validate()
+                    .map_err(|err| format!("{METEOR_FIELD}: {err}"))
+            });
+        if let Err(err) = validation {
+            error!("{err}");
+            exit.send(AppExit);
+            return;
+        }
+
+        let Some((sheet, atlas, image)) = sprite_sheets.get(&game_data.sheet).and_then(|sheet| {
+            let atlas = texture_atlases.get(&sheet.atlas)?;
+            Some((sheet, atlas, images.get(&atlas.texture)?))
+        }) else {
+            return;
+        };
+        // masks are hulled once here, so every run, headless or not, collides the same
+        let textures = sheet_textures(
+            sheet,
+            (enemies, bosses, pickups, weapons, meteors),
+            &mut game_textures,
+        )
+        .and_then(|_| {
+            mask_hulls(
+                sheet,
+                atlas,
+                image,
+                (enemies, bosses, weapons, meteors),
+                &mut game_textures,
+            )
+        });
+        if let Err(err) = textures {
+            error!("{SPRITE_SHEET}: {err}");
+            exit.send(AppExit);
+            return;
+        }
+    }
+    next_state.set(AppState::MainMenu);
+}
+
+/// Look every sprite up by name in the sprite sheet, the data assets name their own
+fn sheet_textures(
+    sheet: &SpriteSheet,
+    (enemies, bosses, pickups, weapons, meteors): (
+        &EnemyArchetypes,
+        &BossLibrary,
+        &LootTable,
+        &WeaponLibrary,
+        &MeteorField,
+    ),
+    game_textures: &mut GameTextures,
+) -> Result<(), String> {
+    game_textures.sheet = sheet.atlas.clone();
+    game_textures.player = sheet.index(PLAYER_SPRITE)?;
+    game_textures.player_engine = sheet.indices(&PLAYER_ENGINE_FRAMES)?;
+    game_textures.player_damage = sheet.indices(&PLAYER_DAMAGE_SPRITES)?;
+    game_textures.player_shield = sheet.indices(&PLAYER_SHIELD_SPRITES)?;
+    game_textures.enemy_laser = sheet.index(ENEMY_LASER_SPRITE)?;
+    game_textures.enemy_orb = sheet.index(ENEMY_ORB_SPRITE)?;
+    game_textures.boss_beam = sheet.index(BOSS_BEAM_SPRITE)?;
+    game_textures.boss_beam_muzzle = sheet.index(BOSS_BEAM_MUZZLE_SPRITE)?;
+    game_textures.explosions = HashMap::from_iter([
+        (
+            ExplosionKind::Enemy,
+            sheet.indices(&ENEMY_EXPLOSION_FRAMES)?,
+        ),
+        (
+            ExplosionKind::Player,
+            sheet.indices(&PLAYER_EXPLOSION_FRAMES)?,
+        ),
+        (
+            ExplosionKind::Meteor,
+            sheet.indices(&METEOR_EXPLOSION_FRAMES)?,
+        ),
+    ]);
+    for archetype in enemies.archetypes.iter() {
+        let sprite = sheet.index(&archetype.sprite)?;
+        game_textures.enemies.insert(archetype.name.clone(), sprite);
+    }
+    for boss in bosses.bosses.iter() {
+        let sprite = sheet.index(&boss.sprite)?;
+        game_textures.bosses.insert(boss.name.clone(), sprite);
+    }
+    for pickup in pickups.pickups.iter() {
+        let sprite = sheet.index(&pickup.sprite)?;
+        game_textures.pickups.insert(pickup.name.clone(), sprite);
+    }
+    for weapon in weapons.weapons.iter() {
+        let sprite = sheet.index(&weapon.sprite)?;
+        game_textures.weapons.insert(weapon.kind, sprite);
+    }
+    for (name, _) in meteors
+        .kinds
+        .iter()
+        .flat_map(|kind| kind.sprites.iter().flatten())
+    {
+        game_textures
+            .meteors
+            .insert(name.clone(), sheet.index(name)?);
+    }
+    Ok(())
+}
+
+/// Hull the `Mask` hitboxes of the player, enemies, bosses, weapons and meteors from the sheet
+/// image
+fn mask_hulls(
+    sheet: &SpriteSheet,
+    atlas: &TextureAtlas,
+    image: &Image,
+    (enemies, bosses, weapons, meteors): (
+        &EnemyArchetypes,
+        &BossLibrary,
+        &WeaponLibrary,
+        &MeteorField,
+    ),
+    game_textures: &mut GameTextures,
+) -> Result<(), String> {
+    let masks = [(PLAYER_SPRITE, &PLAYER_HITBOX)]
+        .into_iter()
+        .chain(
+            enemies
+                .archetypes
+                .iter()
+                .map(|archetype| (archetype.sprite.as_str(), &archetype.hitbox)),
+        )
+        .chain(
+            bosses
+                .bosses
+                .iter()
+                .map(|boss| (boss.sprite.as_str(), &boss.hitbox)),
+        )
+        .chain(
+            weapons
+                .weapons
+                .iter()
+                .map(|weapon| (weapon.sprite.as_str(), &weapon.hitbox)),
+        )
+        // a meteor size has a hull for the sprite of every kind
+        .chain(meteors.kinds.iter().flat_map(|kind| {
+            meteors
+                .sizes
+                .iter()
+                .zip(&kind.sprites)
+                .flat_map(|(size, sprites)| {
+                    sprites
+                        .iter()
+                        .map(move |(sprite, _)| (sprite.as_str(), &size.hitbox))
+                })
+        }));
+    for (name, shape) in masks {
+        let HitboxShape::Mask { threshold } = shape else {
+            continue;
+        };
+        let index = sheet.index(name)?;
+        let hull = atlas
+            .textures
+            .get(index)
+            .and_then(|rect| mask_hull(image, *rect, *threshold))
+            .ok_or_else(|| format!("no mask hull of {name:?} at threshold {threshold}"))?;
+        game_textures
+            .hulls
+            .insert((index, threshold.to_bits()), hull);
+    }
+    Ok(())
+}
+
+/// Convex hull of the pixels with an alpha above `threshold` within `rect`, in sprite pixels
+/// around the center, `None` for images without 8 bit alpha
+fn mask_hull(image: &Image, rect: Rect, threshold: f32) -> Option<Vec<(f32, f32)>> {
+    let size = image.size();
+    let stride = size.x as usize;
+    if image.data.len() != stride * size.y as usize * 4 {
+        return None;
+    }
+    let (x0, y0) = (rect.min.x as usize, rect.min.y as usize);
+    let (w, h) = (rect.width() as usize, rect.height() as usize);
+    let opaque = |x: usize, y: usize| {
+        image.data[((y0 + y) * stride + x0 + x) * 4 + 3] as f32 > threshold * 255.
+    };
+    // the outermost pixels of every row are enough for the hull
+    let mut corners = Vec::new();
+    for y in 0..h {
+        let Some(left) = (0..w).find(|x| opaque(*x, y)) else {
+            continue;
+        };
+        let right = (0..w).rfind(|x| opaque(*x, y)).unwrap_or(left) + 1;
+        for x in [left, right] {
+            for row in [y, y + 1] {
+                corners.push(Vec2::new(
+                    x as f32 - w as f32 / 2.,
+                    h as f32 / 2. - row as f32,
+                ));
+            }
+        }
+    }
+    let hull = convex_hull(corners);
+    (hull.len() >= 3).then(|| hull.into_iter().map(|point| (point.x, point.y)).collect())
+}
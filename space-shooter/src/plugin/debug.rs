@@ -0,0 +1,68 @@
+use crate::{
+    asset::hitbox::HitboxShape,
+    component::{
+        collider::{Collider, Layers},
+        hitbox::{Hitbox, WorldShape},
+        sprite::SpriteSize,
+    },
+};
+use bevy::prelude::*;
+
+/// Draws the collision shapes, toggled with F3 (windowed only)
+pub struct DebugPlugin;
+
+impl Plugin for DebugPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<HitboxOverlay>().add_systems(
+            Update,
+            (
+                hitbox_overlay_toggle_system,
+                hitbox_overlay_system.run_if(|overlay: Res<HitboxOverlay>| overlay.0),
+            ),
+        );
+    }
+}
+
+/// Resource - true while the hitbox overlay is drawn
+#[derive(Resource, Default, Debug)]
+pub struct HitboxOverlay(pub bool);
+
+pub fn hitbox_overlay_toggle_system(kb: Res<Input<KeyCode>>, mut overlay: ResMut<HitboxOverlay>) {
+    if kb.just_pressed(KeyCode::F3) {
+        overlay.0 = !overlay.0;
+    }
+}
+
+pub fn hitbox_overlay_system(
+    mut gizmos: Gizmos,
+    query: Query<(&Transform, &SpriteSize, &Collider, Option<&Hitbox>)>,
+) {
+    for (transform, size, collider, hitbox) in query.iter() {
+        let shape = WorldShape::new(
+            hitbox.map_or(&HitboxShape::Rect, |hitbox| &hitbox.0),
+            size,
+            transform,
+        );
+        let color = match collider.layer {
+            Layers::PLAYER => Color::GREEN,
+            Layers::ENEMY => Color::RED,
+            Layers::PLAYER_PROJECTILE => Color::CYAN,
+            Layers::ENEMY_PROJECTILE => Color::ORANGE,
+            _ => Color::WHITE,
+        };
+        for (from, to) in shape.edges() {
+            if shape.radius > 0. {
+                gizmos.circle_2d(from, shape.radius, color);
+                // sides of a capsule
+                let side = (to - from).perp().normalize_or_zero() * shape.radius;
+                gizmos.line_2d(from + side, to + side, color);
+                gizmos.line_2d(from - side, to - side, color);
+            } else {
+                gizmos.line_2d(from, to, color);
+            }
+        }
+        if shape.radius > 0. && shape.points.len() == 2 {
+            gizmos.circle_2d(shape.points[1], shape.radius, color);
+        }
+    }
+}
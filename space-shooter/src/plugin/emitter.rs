@@ -0,0 +1,247 @@
+use crate::{
+    asset::pattern::{BulletKind, BulletPattern, PatternKind},
+    component::{
+        collider::{Collider, Layers},
+        damage::Damage,
+        emitter::{BulletEmitter, EmitterState, Homing},
+        enemy::{Enemy, EnemyFire, FromEnemy},
+        hitbox::Hitbox,
+        laser::Laser,
+        movement::Movable,
+        player::{FromPlayer, Player},
+        state::InGame,
+        velocity::Velocity,
+    },
+    plugin::{movement::movement_system, state::GameplaySet},
+    resource::{global::GameTextures, playfield::PlayField, pool::SpritePool},
+    ENEMY_LASER_SIZE, ENEMY_ORB_HITBOX, ENEMY_ORB_SIZE, LASER_HITBOX, SPRITE_SCALE,
+};
+use bevy::{ecs::query::Has, prelude::*};
+use std::f32::consts::{FRAC_PI_2, PI, TAU};
+
+pub struct EmitterPlugin;
+
+impl Plugin for EmitterPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            FixedUpdate,
+            (
+                bullet_emitter_system,
+                // steer before the velocity is applied
+                homing_system.before(movement_system),
+            )
+                .in_set(GameplaySet),
+        );
+    }
+}
+
+/// Fire every pattern of the emitters as their cooldowns run out
+#[allow(clippy::too_many_arguments)]
+pub fn bullet_emitter_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    game_textures: Res<GameTextures>,
+    playfield: Res<PlayField>,
+    mut pool: ResMut<SpritePool>,
+    query_audio: Query<&AudioSink, With<EnemyFire>>,
+    player_query: Query<&Transform, With<Player>>,
+    mut emitter_query: Query<(&Transform, &mut BulletEmitter)>,
+) {
+    let time_step = time.delta_seconds();
+    let player = player_query
+        .get_single()
+        .ok()
+        .map(|tf| tf.translation.truncate());
+    for (transform, mut emitter) in emitter_query.iter_mut() {
+        // hold fire until on screen
+        if !playfield.contains(transform.translation, 0.) {
+            continue;
+        }
+        let origin = transform.translation.truncate();
+        let BulletEmitter { patterns, states } = &mut *emitter;
+        for (pattern, state) in patterns.iter().zip(states.iter_mut()) {
+            state.cooldown -= time_step;
+            if state.cooldown > 0. {
+                continue;
+            }
+            state.cooldown += match pattern.kind {
+                PatternKind::Burst { shots, gap, .. } => {
+                    state.shots += 1;
+                    if state.shots < shots {
+                        gap
+                    } else {
+                        state.shots = 0;
+                        pattern.interval
+                    }
+                }
+                _ => pattern.interval,
+            };
+
+            if let Ok(sink) = query_audio.get_single() {
+                sink.play();
+            }
+            let headings = volley_headings(&pattern.kind, state, origin, player);
+            let homing = volley_homing(&pattern.kind);
+            for muzzle in pattern.muzzles.iter() {
+                let position = origin + Vec2::new(*muzzle, 0.);
+                for heading in headings.iter() {
+                    spawn_bullet(
+                        &mut commands,
+                        &mut pool,
+                        &game_textures,
+                        pattern,
+                        position,
+                        *heading,
+                        homing,
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// Headings of one volley of `kind` fired from `origin`, radians
+fn volley_headings(
+    kind: &PatternKind,
+    state: &mut EmitterState,
+    origin: Vec2,
+    player: Option<Vec2>,
+) -> Vec<f32> {
+    let aim = player
+        .and_then(|player| (player - origin).try_normalize())
+        .map_or(-FRAC_PI_2, |aim| aim.y.atan2(aim.x));
+    match kind {
+        PatternKind::Spread { count, spread } => fan(-FRAC_PI_2, *count, *spread),
+        PatternKind::Aimed { count, spread } | PatternKind::Homing { count, spread, .. } => {
+            fan(aim, *count, *spread)
+        }
+        PatternKind::Spiral { arms, turn } => {
+            let headings = (0..*arms)
+                .map(|arm| state.turn + TAU * arm as f32 / *arms as f32)
+                .collect();
+            state.turn = (state.turn + turn.to_radians()) % TAU;
+            headings
+        }
+        PatternKind::Burst { pattern, .. } => volley_headings(pattern, state, origin, player),
+    }
+}
+
+/// `count` headings spread evenly over `spread` degrees around `center`
+pub fn fan(center: f32, count: u32, spread: f32) -> Vec<f32> {
+    (0..count)
+        .map(|index| {
+            let fraction = if count > 1 {
+                index as f32 / (count - 1) as f32 - 0.5
+            } else {
+                0.
+            };
+            center + spread.to_radians() * fraction
+        })
+        .collect()
+}
+
+fn volley_homing(kind: &PatternKind) -> Option<Homing> {
+    match kind {
+        PatternKind::Homing {
+            turn_rate,
+            lifetime,
+            ..
+        } => Some(Homing {
+            turn_rate: turn_rate.to_radians(),
+            remaining: *lifetime,
+        }),
+        PatternKind::Burst { pattern, .. } => volley_homing(pattern),
+        _ => None,
+    }
+}
+
+fn spawn_bullet(
+    commands: &mut Commands,
+    pool: &mut SpritePool,
+    game_textures: &GameTextures,
+    pattern: &BulletPattern,
+    position: Vec2,
+    heading: f32,
+    homing: Option<Homing>,
+) {
+    let (index, size, hitbox) = match pattern.bullet {
+        BulletKind::Laser => (game_textures.enemy_laser, ENEMY_LASER_SIZE, LASER_HITBOX),
+        BulletKind::Orb => (game_textures.enemy_orb, ENEMY_ORB_SIZE, ENEMY_ORB_HITBOX),
+    };
+    let velocity = Vec2::from_angle(heading) * pattern.speed;
+    let mut bullet = pool.acquire(commands);
+    bullet
+        .insert(SpriteSheetBundle {
+            sprite: TextureAtlasSprite::new(index),
+            texture_atlas: game_textures.sheet.clone(),
+            transform: Transform {
+                translation: position.extend(9.),
+                scale: Vec3::new(SPRITE_SCALE, SPRITE_SCALE, 1.),
+                // sprites point up
+                rotation: Quat::from_rotation_z(heading - FRAC_PI_2),
+            },
+            ..Default::default()
+        })
+        .insert(Laser)
+        .insert(FromEnemy)
+        .insert(InGame)
+        .insert(size)
+        .insert(Hitbox(hitbox))
+        .insert(Collider::passive(Layers::ENEMY_PROJECTILE))
+        .insert(Damage(pattern.damage))
+        .insert(Movable { auto_despawn: true })
+        .insert(Velocity {
+            x: velocity.x,
+            y: velocity.y,
+        });
+    if let Some(homing) = homing {
+        bullet.insert(homing);
+    }
+}
+
+/// Turn homing projectiles towards their target, no faster than their turn rate
+#[allow(clippy::type_complexity)]
+pub fn homing_system(
+    time: Res<Time>,
+    player_query: Query<&Transform, (With<Player>, Without<Homing>)>,
+    enemy_query: Query<&Transform, (With<Enemy>, Without<Homing>)>,
+    mut query: Query<(&mut Transform, &mut Velocity, &mut Homing, Has<FromPlayer>)>,
+) {
+    let time_step = time.delta_seconds();
+    let player = player_query.get_single().ok();
+    for (mut transform, mut velocity, mut homing, from_player) in query.iter_mut() {
+        if homing.remaining <= 0. {
+            continue;
+        }
+        // player missiles go for the nearest enemy
+        let target = if from_player {
+            enemy_query.iter().min_by(|a, b| {
+                let distance =
+                    |tf: &&Transform| tf.translation.distance_squared(transform.translation);
+                distance(a).total_cmp(&distance(b))
+            })
+        } else {
+            player
+        };
+        let Some(target_tf) = target else {
+            continue;
+        };
+        homing.remaining -= time_step;
+        let current = Vec2::new(velocity.x, velocity.y);
+        let to_target = (target_tf.translation - transform.translation).truncate();
+        let heading = current.y.atan2(current.x);
+        // shortest way round, within (-PI, PI]
+        let mut turn = to_target.y.atan2(to_target.x) - heading;
+        if turn > PI {
+            turn -= TAU;
+        } else if turn <= -PI {
+            turn += TAU;
+        }
+        let turn_limit = homing.turn_rate * time_step;
+        let heading = heading + turn.clamp(-turn_limit, turn_limit);
+        let next = Vec2::from_angle(heading) * current.length();
+        velocity.x = next.x;
+        velocity.y = next.y;
+        transform.rotation = Quat::from_rotation_z(heading - FRAC_PI_2);
+    }
+}
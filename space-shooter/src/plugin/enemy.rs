@@ -1,33 +1,42 @@
 use crate::{
+    asset::{enemy::EnemyArchetypes, formation::FormationSpec, path::PathLibrary},
     component::{
-        enemy::{Enemy, EnemyFire, FromEnemy},
-        fomation::{Formation, FormationMaker},
-        laser::Laser,
+        collider::{Collider, Layers},
+        damage::HitFlash,
+        emitter::BulletEmitter,
+        enemy::{Enemy, EnemyFire, EnemyStats},
+        fomation::{Formation, FormationMaker, FormationMotion},
+        health::Health,
         movement::Movable,
+        player::Player,
+        state::InGame,
         velocity::Velocity,
     },
-    resource::global::GameTextures,
-    ENEMY_LASER_SIZE, ENEMY_SIZE, SPRITE_SCALE,
+    plugin::state::{AppState, GameplaySet},
+    resource::{
+        config::windowed, data::GameData, global::GameTextures, playfield::PlayField, rng::GameRng,
+        wave::WaveDirector,
+    },
+    SPRITE_SCALE,
 };
-use bevy::{audio::Volume, prelude::*, time::common_conditions::on_timer, window::PrimaryWindow};
+use bevy::{audio::Volume, prelude::*};
 use rand::prelude::*;
-use std::{f32::consts::PI, time::Duration};
+use std::f32::consts::{PI, TAU};
 
 pub struct EnemyPlugin;
 
 impl Plugin for EnemyPlugin {
     fn build(&self, app: &mut App) {
         app.insert_resource(FormationMaker::default())
-            .add_systems(Startup, enemy_audio_spawn_system)
-            .add_systems(
-                Update,
-                enemy_spawn_system.run_if(on_timer(Duration::from_millis(800))),
-            )
+            .add_systems(Startup, enemy_audio_spawn_system.run_if(windowed))
+            .add_systems(OnEnter(AppState::MainMenu), enemy_reset_system)
             .add_systems(
-                Update,
-                enemy_fire_system.run_if(|| thread_rng().gen_bool(1. / 60.)),
-            )
-            .add_systems(Update, enemy_move_system);
+                FixedUpdate,
+                (enemy_spawn_system, enemy_move_system)
+                    // fixed order, so the shared GameRng is drawn from deterministically
+                    .chain()
+                    .in_set(GameplaySet),
+            );
     }
 }
 
@@ -41,118 +50,246 @@ pub fn enemy_audio_spawn_system(mut commands: Commands, asset_server: Res<AssetS
     ));
 }
 
+pub fn enemy_reset_system(mut formation_maker: ResMut<FormationMaker>) {
+    *formation_maker = FormationMaker::default();
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn enemy_spawn_system(
     mut commands: Commands,
     game_textures: Res<GameTextures>,
+    game_data: Res<GameData>,
+    enemy_archetypes: Res<Assets<EnemyArchetypes>>,
+    path_libraries: Res<Assets<PathLibrary>>,
     mut formation_maker: ResMut<FormationMaker>,
-    mut win_query: Query<&mut Window, With<PrimaryWindow>>,
+    mut wave_director: ResMut<WaveDirector>,
+    mut rng: ResMut<GameRng>,
+    playfield: Res<PlayField>,
     enemy_query: Query<Entity, With<Enemy>>,
 ) {
-    if enemy_query.iter().len() >= 2 {
+    let (Some(enemy_archetypes), Some(paths)) = (
+        enemy_archetypes.get(&game_data.enemies),
+        path_libraries.get(&game_data.paths),
+    ) else {
+        return;
+    };
+    let alive = enemy_query.iter().len() as u32;
+    if !wave_director.can_spawn(alive) {
         return;
     }
-    let win = win_query.single_mut();
-    // get formation and start x/y
-    let formation = formation_maker.make(&win);
-    let (x, y) = formation.start;
-    commands
-        .spawn(SpriteBundle {
-            texture: game_textures.enemy.clone(),
-            transform: Transform {
-                translation: Vec3::new(x, y, 10.),
-                scale: Vec3::new(SPRITE_SCALE, SPRITE_SCALE, 1.),
-                rotation: Quat::from_rotation_x(PI),
+    // pick the archetype from the wave mix, or from all archetypes
+    let wave_enemies = wave_director
+        .def
+        .as_ref()
+        .map_or(&[][..], |def| &def.enemies[..]);
+    let archetype = if wave_enemies.is_empty() {
+        enemy_archetypes
+            .archetypes
+            .choose_weighted(&mut **rng, |archetype| archetype.weight)
+            .ok()
+    } else {
+        wave_enemies
+            .choose_weighted(&mut **rng, |(_, weight)| *weight)
+            .ok()
+            .and_then(|(name, _)| enemy_archetypes.get(name))
+    };
+    // the spawn is only claimed once there is an archetype to spawn
+    let Some(archetype) = archetype else {
+        return;
+    };
+    wave_director.take_spawn(alive);
+    // pick the formation kind, the whole formation shares the archetype
+    let default_spec = FormationSpec::default();
+    let spec = match &wave_director.def {
+        Some(def) if !def.kinds.is_empty() => def
+            .kinds
+            .choose_weighted(&mut **rng, |spec| spec.weight)
+            .unwrap_or(&default_spec),
+        _ => &default_spec,
+    };
+    let sprite = game_textures
+        .enemies
+        .get(&archetype.name)
+        .copied()
+        .unwrap_or_default();
+    for formation in formation_maker.make(spec, archetype.speed, paths, &playfield, &mut **rng) {
+        let (x, y) = formation.start;
+        commands
+            .spawn(SpriteSheetBundle {
+                sprite: TextureAtlasSprite::new(sprite),
+                texture_atlas: game_textures.sheet.clone(),
+                transform: Transform {
+                    translation: Vec3::new(x, y, 10.),
+                    scale: Vec3::new(SPRITE_SCALE, SPRITE_SCALE, 1.),
+                    rotation: Quat::from_rotation_x(PI),
+                },
                 ..Default::default()
-            },
-            ..Default::default()
-        })
-        .insert(Enemy)
-        .insert(ENEMY_SIZE)
-        .insert(Movable { auto_despawn: true })
-        .insert(Velocity::default())
-        .insert(formation);
+            })
+            .insert(Enemy)
+            .insert(InGame)
+            .insert(archetype.sprite_size())
+            .insert(game_textures.hitbox(&archetype.hitbox, sprite))
+            .insert(Collider::new(Layers::ENEMY, Layers::PLAYER_PROJECTILE))
+            .insert(Health::new(archetype.hp))
+            .insert(HitFlash::default())
+            .insert(EnemyStats {
+                score: archetype.score,
+            })
+            // members of a formation do not fire in sync
+            .insert(BulletEmitter::new(archetype.fire.clone()).with_delay(rng.gen_range(0. ..1.)))
+            // the formation decides when its members leave
+            .insert(Movable {
+                auto_despawn: false,
+            })
+            .insert(Velocity::default())
+            .insert(formation);
+    }
 }
 
-pub fn enemy_fire_system(
+pub fn enemy_move_system(
     mut commands: Commands,
-    game_textures: Res<GameTextures>,
-    query_audio: Query<&AudioSink, With<EnemyFire>>,
-    enemy_query: Query<&mut Transform, With<Enemy>>,
+    time: Res<Time>,
+    playfield: Res<PlayField>,
+    player_query: Query<&Transform, (With<Player>, Without<Enemy>)>,
+    mut query: Query<(Entity, &mut Transform, &mut Formation), With<Enemy>>,
 ) {
-    for enemy_tf in enemy_query.iter() {
-        if thread_rng().gen_bool(3. / 10.) {
+    let time_step = time.delta_seconds();
+    let player_x = player_query.get_single().ok().map(|tf| tf.translation.x);
+    for (entity, mut transform, mut formation) in query.iter_mut() {
+        let formation = &mut *formation;
+        // members wait off screen for their turn
+        formation.elapsed += time_step;
+        if formation.elapsed < 0. {
             continue;
-        };
-        let (enemy_w, enemy_h) = (enemy_tf.translation.x, enemy_tf.translation.y);
-        let mut spawn_laser = |muzzle_offset| {
-            if let Ok(sink) = query_audio.get_single() {
-                sink.play();
-            }
-            commands
-                .spawn(SpriteBundle {
-                    texture: game_textures.enemy_laser.clone(),
-                    transform: Transform {
-                        translation: Vec3::new(enemy_w + muzzle_offset, enemy_h, 9.),
-                        scale: Vec3::new(SPRITE_SCALE, SPRITE_SCALE, 1.),
-                        rotation: Quat::from_rotation_x(PI),
-                        ..Default::default()
-                    },
-                    ..Default::default()
-                })
-                .insert(Laser)
-                .insert(FromEnemy)
-                .insert(ENEMY_LASER_SIZE)
-                .insert(Movable { auto_despawn: true })
-                .insert(Velocity { x: 0., y: -3. });
-        };
-        spawn_laser(0.);
-    }
-}
+        }
+        let elapsed = formation.elapsed;
 
-pub fn enemy_move_system(mut query: Query<(&mut Transform, &mut Formation), With<Enemy>>) {
-    let time_step = 1. / 60.;
-    for (mut transform, mut formation) in query.iter_mut() {
         // current position
-        let (x_org, y_org) = (transform.translation.x, transform.translation.y);
+        let position = transform.translation.truncate();
 
         // max distance
         let max_distance = time_step * formation.speed;
 
-        // 1 for counter clockwise, -1 clockwise
-        let dir: f32 = if formation.start.0 < 0. { 1. } else { -1. };
-        let (x_pivot, y_pivot) = formation.pivot;
-        let (x_radius, y_radius) = formation.radius;
-
-        // compute next angle (based on time for now)
-        let angle = formation.angle
-            + dir * formation.speed * time_step / (x_radius.min(y_radius) * PI / 2.);
-
-        // compute target x/y
-        let x_dst = x_radius * angle.cos() + x_pivot;
-        let y_dst = y_radius * angle.sin() + y_pivot;
-
-        // compute distance
-        let dx = x_org - x_dst;
-        let dy = y_org - y_dst;
-        let distance = (dx * dx + dy * dy).sqrt();
-        let distance_ratio = if distance != 0. {
-            max_distance / distance
-        } else {
-            0.
+        let speed = formation.speed;
+        let start = formation.start;
+        let next = match &mut formation.motion {
+            FormationMotion::Orbit {
+                radius,
+                pivot,
+                angle,
+            } => orbit_step(position, start, *radius, *pivot, angle, speed, time_step),
+            FormationMotion::Hold { slot, sway } => {
+                let target = *slot + Vec2::new(*sway * (elapsed * 0.5).sin(), 0.);
+                step_towards(position, target, max_distance)
+            }
+            FormationMotion::Sine {
+                y,
+                amplitude,
+                wavelength,
+                dir,
+            } => {
+                let travelled = speed * elapsed;
+                let x = start.0 + *dir * travelled;
+                if x.abs() > playfield.w / 2. + 150. {
+                    commands.entity(entity).despawn();
+                    continue;
+                }
+                Vec2::new(x, *y + *amplitude * (travelled / *wavelength * TAU).sin())
+            }
+            FormationMotion::Dive { slot, hold, target } => match target {
+                None => {
+                    let next = step_towards(position, *slot, max_distance);
+                    if elapsed >= *hold && next == *slot {
+                        *target = Some(player_x.unwrap_or(slot.x));
+                    }
+                    next
+                }
+                Some(target_x) => {
+                    // fast dive, bending towards where the player was
+                    let mut next = position;
+                    next.x += (*target_x - position.x).clamp(-max_distance, max_distance);
+                    next.y -= max_distance * 1.5;
+                    if next.y < -playfield.h / 2. - 100. {
+                        // come back in from the top and wait again
+                        *target = None;
+                        formation.elapsed = 0.;
+                        next = Vec2::new(slot.x, playfield.h / 2. + 100.);
+                    }
+                    next
+                }
+            },
+            FormationMotion::Path(follower) => match follower.advance(time_step) {
+                Some(next) => next,
+                None => {
+                    commands.entity(entity).despawn();
+                    continue;
+                }
+            },
         };
 
-        // compute final x/y
-        let x = x_org - dx * distance_ratio;
-        let x = if dx > 0. { x.max(x_dst) } else { x.min(x_dst) };
-        let y = y_org - dy * distance_ratio;
-        let y = if dy > 0. { y.max(y_dst) } else { y.min(y_dst) };
+        let translation = &mut transform.translation;
+        (translation.x, translation.y) = (next.x, next.y);
+    }
+}
 
-        // start rotating the formation angle only when sprite is on or close to ellipse
-        if distance < max_distance * formation.speed / 20. {
-            formation.angle = angle;
-        }
+/// Move at most `max_distance` from `position` towards `target`
+fn step_towards(position: Vec2, target: Vec2, max_distance: f32) -> Vec2 {
+    let delta = target - position;
+    if delta.length() <= max_distance {
+        target
+    } else {
+        position + delta.normalize() * max_distance
+    }
+}
 
-        let translation = &mut transform.translation;
-        (translation.x, translation.y) = (x, y);
+/// Approach the ellipse, then follow it
+fn orbit_step(
+    position: Vec2,
+    start: (f32, f32),
+    radius: (f32, f32),
+    pivot: (f32, f32),
+    formation_angle: &mut f32,
+    speed: f32,
+    time_step: f32,
+) -> Vec2 {
+    // current position
+    let (x_org, y_org) = (position.x, position.y);
+
+    // max distance
+    let max_distance = time_step * speed;
+
+    // 1 for counter clockwise, -1 clockwise
+    let dir: f32 = if start.0 < 0. { 1. } else { -1. };
+    let (x_pivot, y_pivot) = pivot;
+    let (x_radius, y_radius) = radius;
+
+    // compute next angle (based on time for now)
+    let angle = *formation_angle + dir * speed * time_step / (x_radius.min(y_radius) * PI / 2.);
+
+    // compute target x/y
+    let x_dst = x_radius * angle.cos() + x_pivot;
+    let y_dst = y_radius * angle.sin() + y_pivot;
+
+    // compute distance
+    let dx = x_org - x_dst;
+    let dy = y_org - y_dst;
+    let distance = (dx * dx + dy * dy).sqrt();
+    let distance_ratio = if distance != 0. {
+        max_distance / distance
+    } else {
+        0.
+    };
+
+    // compute final x/y
+    let x = x_org - dx * distance_ratio;
+    let x = if dx > 0. { x.max(x_dst) } else { x.min(x_dst) };
+    let y = y_org - dy * distance_ratio;
+    let y = if dy > 0. { y.max(y_dst) } else { y.min(y_dst) };
+
+    // start rotating the formation angle only when sprite is on or close to ellipse
+    if distance < max_distance * speed / 20. {
+        *formation_angle = angle;
     }
+
+    Vec2::new(x, y)
 }
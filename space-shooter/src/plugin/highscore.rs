@@ -0,0 +1,95 @@
+use crate::{
+    component::state::StateScreen,
+    plugin::state::AppState,
+    resource::{
+        config::GameConfig,
+        global::PlayerState,
+        highscore::{format_date, HighScores},
+    },
+};
+use bevy::prelude::*;
+
+pub struct HighScorePlugin;
+
+impl Plugin for HighScorePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, highscore_load_system.run_if(persistent))
+            .add_systems(
+                OnEnter(AppState::GameOver),
+                (
+                    highscore_record_system.run_if(persistent),
+                    highscore_screen_system.run_if(resource_exists::<HighScores>()),
+                )
+                    // the table shown includes the game just over
+                    .chain(),
+            );
+    }
+}
+
+/// Run condition - only real games played in a window go into the table
+fn persistent(config: Res<GameConfig>) -> bool {
+    !config.headless && config.replay.is_none()
+}
+
+pub fn highscore_load_system(mut commands: Commands) {
+    let high_scores = HighScores::load(HighScores::default_path());
+    info!(
+        "{} high scores loaded from {:?}",
+        high_scores.entries.len(),
+        high_scores.path
+    );
+    commands.insert_resource(high_scores);
+}
+
+pub fn highscore_record_system(
+    config: Res<GameConfig>,
+    player_state: Res<PlayerState>,
+    mut high_scores: ResMut<HighScores>,
+) {
+    if let Some(rank) = high_scores.record(&config.name, player_state.score) {
+        info!("new high score #{}: {}", rank + 1, player_state.score);
+    }
+    if let Err(err) = high_scores.save() {
+        error!("{err}");
+    }
+}
+
+pub fn highscore_screen_system(mut commands: Commands, high_scores: Res<HighScores>) {
+    let mut table = String::from("HIGH SCORES\n\n");
+    for (rank, entry) in high_scores.entries.iter().enumerate() {
+        let marker = if high_scores.last_rank == Some(rank) {
+            ">"
+        } else {
+            " "
+        };
+        table += &format!(
+            "{marker}{:>2}. {:<12} {:>8}  {}\n",
+            rank + 1,
+            entry.name.chars().take(12).collect::<String>(),
+            entry.score,
+            format_date(entry.date)
+        );
+    }
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                bottom: Val::Px(40.),
+                width: Val::Percent(100.),
+                justify_content: JustifyContent::Center,
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .insert(StateScreen)
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                table,
+                TextStyle {
+                    font_size: 18.,
+                    color: Color::WHITE,
+                    ..Default::default()
+                },
+            ));
+        });
+}
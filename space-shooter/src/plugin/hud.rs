@@ -0,0 +1,452 @@
+use crate::{
+    component::{
+        boss::Boss,
+        health::Health,
+        hud::{
+            HudBossBar, HudBossFill, HudBossName, HudCombo, HudLives, HudPowerUpTimer, HudPowerUps,
+            HudScore, HudWave, HudWeapon, HudWeaponName,
+        },
+        state::InGame,
+    },
+    event::{
+        player::{ComboChanged, LivesChanged, ScoreChanged, WeaponChanged},
+        wave::WaveStarted,
+    },
+    plugin::state::AppState,
+    resource::{
+        global::{GameTextures, HudAssets, PlayerState},
+        pickup::PowerUps,
+    },
+    HUD_FONT, HUD_LIFE_SIZE, HUD_LIFE_SPRITE, HUD_NUMERAL_SIZE, HUD_NUMERAL_X_SPRITE,
+    HUD_POWER_UP_SIZE, WINDOW_SIZE,
+};
+use bevy::{prelude::*, window::WindowResized};
+
+pub struct HudPlugin;
+
+impl Plugin for HudPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, hud_assets_system)
+            .add_systems(OnExit(AppState::MainMenu), hud_spawn_system)
+            .add_systems(
+                Update,
+                (
+                    hud_score_system,
+                    hud_combo_system,
+                    hud_lives_system,
+                    hud_wave_system,
+                    hud_weapon_system,
+                    hud_power_up_system,
+                    hud_boss_system,
+                    hud_scale_system,
+                ),
+            );
+    }
+}
+
+pub fn hud_assets_system(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(HudAssets {
+        font: asset_server.load(HUD_FONT),
+        life: asset_server.load(HUD_LIFE_SPRITE),
+        numerals: (0..10)
+            .map(|digit| asset_server.load(format!("PNG/UI/numeral{digit}.png")))
+            .collect(),
+        numeral_x: asset_server.load(HUD_NUMERAL_X_SPRITE),
+    });
+}
+
+pub fn hud_spawn_system(
+    mut commands: Commands,
+    hud_assets: Res<HudAssets>,
+    player_state: Res<PlayerState>,
+) {
+    let text_style = TextStyle {
+        font: hud_assets.font.clone(),
+        font_size: 18.,
+        color: Color::WHITE,
+    };
+    let row = || NodeBundle {
+        style: Style {
+            align_items: AlignItems::Center,
+            column_gap: Val::Px(4.),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                width: Val::Percent(100.),
+                padding: UiRect::all(Val::Px(10.)),
+                justify_content: JustifyContent::SpaceBetween,
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .insert(InGame)
+        .with_children(|parent| {
+            // left: score and combo, wave and weapon level, active power-ups
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Column,
+                        row_gap: Val::Px(6.),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                })
+                .with_children(|parent| {
+                    parent.spawn(row()).with_children(|parent| {
+                        parent
+                            .spawn(TextBundle::from_sections([
+                                TextSection::new("SCORE ", text_style.clone()),
+                                TextSection::new(
+                                    player_state.score.to_string(),
+                                    text_style.clone(),
+                                ),
+                            ]))
+                            .insert(HudScore);
+                        parent
+                            .spawn(TextBundle::from_section(
+                                combo_label(player_state.combo),
+                                TextStyle {
+                                    color: Color::GOLD,
+                                    ..text_style.clone()
+                                },
+                            ))
+                            .insert(HudCombo);
+                    });
+                    parent.spawn(row()).with_children(|parent| {
+                        parent.spawn(TextBundle::from_section("WAVE", text_style.clone()));
+                        parent.spawn(row()).insert(HudWave).with_children(|parent| {
+                            spawn_numerals(parent, &hud_assets, 1);
+                        });
+                    });
+                    parent.spawn(row()).with_children(|parent| {
+                        parent
+                            .spawn(TextBundle::from_section("WEAPON", text_style.clone()))
+                            .insert(HudWeaponName);
+                        parent
+                            .spawn(row())
+                            .insert(HudWeapon)
+                            .with_children(|parent| {
+                                spawn_numerals(parent, &hud_assets, 1);
+                            });
+                    });
+                    parent.spawn(row()).insert(HudPowerUps);
+                });
+            // right: one ship icon per remaining life
+            parent
+                .spawn(row())
+                .insert(HudLives)
+                .with_children(|parent| {
+                    spawn_lives(parent, &hud_assets, player_state.lives);
+                });
+            // bottom: boss name and health bar
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        bottom: Val::Px(12.),
+                        left: Val::Percent(20.),
+                        width: Val::Percent(60.),
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        row_gap: Val::Px(4.),
+                        ..Default::default()
+                    },
+                    visibility: Visibility::Hidden,
+                    ..Default::default()
+                })
+                .insert(HudBossBar)
+                .with_children(|parent| {
+                    parent
+                        .spawn(TextBundle::from_section("", text_style.clone()))
+                        .insert(HudBossName);
+                    parent
+                        .spawn(NodeBundle {
+                            style: Style {
+                                width: Val::Percent(100.),
+                                height: Val::Px(10.),
+                                border: UiRect::all(Val::Px(1.)),
+                                ..Default::default()
+                            },
+                            background_color: Color::rgba(0., 0., 0., 0.5).into(),
+                            border_color: Color::WHITE.into(),
+                            ..Default::default()
+                        })
+                        .with_children(|parent| {
+                            parent
+                                .spawn(NodeBundle {
+                                    style: Style {
+                                        width: Val::Percent(100.),
+                                        height: Val::Percent(100.),
+                                        ..Default::default()
+                                    },
+                                    background_color: Color::CRIMSON.into(),
+                                    ..Default::default()
+                                })
+                                .insert(HudBossFill);
+                        });
+                });
+        });
+}
+
+pub fn hud_score_system(
+    mut score_events: EventReader<ScoreChanged>,
+    mut query: Query<&mut Text, With<HudScore>>,
+) {
+    if let Some(score_changed) = score_events.read().last() {
+        for mut text in query.iter_mut() {
+            text.sections[1].value = score_changed.score.to_string();
+        }
+    }
+}
+
+pub fn hud_combo_system(
+    mut combo_events: EventReader<ComboChanged>,
+    mut query: Query<&mut Text, With<HudCombo>>,
+) {
+    if let Some(combo_changed) = combo_events.read().last() {
+        for mut text in query.iter_mut() {
+            text.sections[0].value = combo_label(combo_changed.combo);
+        }
+    }
+}
+
+pub fn hud_lives_system(
+    mut commands: Commands,
+    hud_assets: Res<HudAssets>,
+    mut lives_events: EventReader<LivesChanged>,
+    query: Query<Entity, With<HudLives>>,
+) {
+    if let Some(lives_changed) = lives_events.read().last() {
+        for entity in query.iter() {
+            commands
+                .entity(entity)
+                .despawn_descendants()
+                .with_children(|parent| spawn_lives(parent, &hud_assets, lives_changed.lives));
+        }
+    }
+}
+
+pub fn hud_wave_system(
+    mut commands: Commands,
+    hud_assets: Res<HudAssets>,
+    mut wave_events: EventReader<WaveStarted>,
+    query: Query<Entity, With<HudWave>>,
+) {
+    if let Some(wave_started) = wave_events.read().last() {
+        for entity in query.iter() {
+            commands
+                .entity(entity)
+                .despawn_descendants()
+                .with_children(|parent| spawn_numerals(parent, &hud_assets, wave_started.wave));
+        }
+    }
+}
+
+pub fn hud_weapon_system(
+    mut commands: Commands,
+    hud_assets: Res<HudAssets>,
+    mut weapon_events: EventReader<WeaponChanged>,
+    query: Query<Entity, With<HudWeapon>>,
+    mut name_query: Query<&mut Text, With<HudWeaponName>>,
+) {
+    if let Some(weapon_changed) = weapon_events.read().last() {
+        for mut text in name_query.iter_mut() {
+            text.sections[0].value = weapon_changed.name.to_uppercase();
+        }
+        for entity in query.iter() {
+            commands
+                .entity(entity)
+                .despawn_descendants()
+                .with_children(|parent| spawn_numerals(parent, &hud_assets, weapon_changed.level));
+        }
+    }
+}
+
+/// One icon per active power-up, with its stacks and a bar of the time left
+pub fn hud_power_up_system(
+    mut commands: Commands,
+    hud_assets: Res<HudAssets>,
+    game_textures: Res<GameTextures>,
+    power_ups: Res<PowerUps>,
+    mut shown: Local<Vec<(String, u32)>>,
+    row_query: Query<Entity, With<HudPowerUps>>,
+    mut timer_query: Query<(&mut Style, &HudPowerUpTimer)>,
+) {
+    let active: Vec<(String, u32)> = power_ups
+        .active
+        .iter()
+        .map(|active| (active.name.clone(), active.stacks))
+        .collect();
+    // rebuild the row only when a power-up comes, goes or stacks
+    if *shown != active {
+        for entity in row_query.iter() {
+            commands
+                .entity(entity)
+                .despawn_descendants()
+                .with_children(|parent| {
+                    for (name, stacks) in active.iter() {
+                        spawn_power_up(parent, &hud_assets, &game_textures, name, *stacks);
+                    }
+                });
+        }
+        *shown = active;
+    }
+    for (mut style, timer) in timer_query.iter_mut() {
+        if let Some(active) = power_ups
+            .active
+            .iter()
+            .find(|active| active.name == timer.0)
+        {
+            style.width = Val::Percent(100. * active.remaining / active.duration.max(f32::EPSILON));
+        }
+    }
+}
+
+/// Show the health bar while a boss is around
+#[allow(clippy::type_complexity)]
+pub fn hud_boss_system(
+    boss_query: Query<(&Boss, &Health)>,
+    mut bar_query: Query<&mut Visibility, With<HudBossBar>>,
+    mut fill_query: Query<&mut Style, With<HudBossFill>>,
+    mut name_query: Query<&mut Text, With<HudBossName>>,
+) {
+    let boss = boss_query.iter().next();
+    for mut visibility in bar_query.iter_mut() {
+        *visibility = if boss.is_some() {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+    }
+    let Some((boss, health)) = boss else {
+        return;
+    };
+    for mut style in fill_query.iter_mut() {
+        style.width = Val::Percent(100. * health.hp as f32 / health.max.max(1) as f32);
+    }
+    for mut text in name_query.iter_mut() {
+        let name = boss.name.to_uppercase().replace('_', " ");
+        if text.sections[0].value != name {
+            text.sections[0].value = name;
+        }
+    }
+}
+
+/// Keep the HUD proportional to the window, the layout is designed for `WINDOW_SIZE`
+pub fn hud_scale_system(
+    mut resize_events: EventReader<WindowResized>,
+    mut ui_scale: ResMut<UiScale>,
+) {
+    if let Some(resized) = resize_events.read().last() {
+        let scale = (resized.width / WINDOW_SIZE.0).min(resized.height / WINDOW_SIZE.1);
+        ui_scale.0 = scale.max(0.25) as f64;
+    }
+}
+
+fn combo_label(combo: u32) -> String {
+    if combo > 1 {
+        format!("x{combo}")
+    } else {
+        String::new()
+    }
+}
+
+fn spawn_lives(parent: &mut ChildBuilder, hud_assets: &HudAssets, lives: u32) {
+    for _ in 0..lives {
+        parent.spawn(ImageBundle {
+            style: Style {
+                width: Val::Px(HUD_LIFE_SIZE.w),
+                height: Val::Px(HUD_LIFE_SIZE.h),
+                ..Default::default()
+            },
+            image: UiImage::new(hud_assets.life.clone()),
+            ..Default::default()
+        });
+    }
+}
+
+/// Draw `value` with the numeral sprites, prefixed by the `X` numeral
+fn spawn_numerals(parent: &mut ChildBuilder, hud_assets: &HudAssets, value: u32) {
+    let digits = value.to_string();
+    let images = std::iter::once(hud_assets.numeral_x.clone()).chain(
+        digits
+            .bytes()
+            .map(|digit| hud_assets.numerals[(digit - b'0') as usize].clone()),
+    );
+    for image in images {
+        parent.spawn(ImageBundle {
+            style: Style {
+                width: Val::Px(HUD_NUMERAL_SIZE.w),
+                height: Val::Px(HUD_NUMERAL_SIZE.h),
+                ..Default::default()
+            },
+            image: UiImage::new(image),
+            ..Default::default()
+        });
+    }
+}
+
+/// Power-up icon over its timer bar, followed by its stacks when more than one
+fn spawn_power_up(
+    parent: &mut ChildBuilder,
+    hud_assets: &HudAssets,
+    game_textures: &GameTextures,
+    name: &str,
+    stacks: u32,
+) {
+    parent
+        .spawn(NodeBundle {
+            style: Style {
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(2.),
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .with_children(|parent| {
+            parent.spawn(AtlasImageBundle {
+                style: Style {
+                    width: Val::Px(HUD_POWER_UP_SIZE.w),
+                    height: Val::Px(HUD_POWER_UP_SIZE.h),
+                    ..Default::default()
+                },
+                texture_atlas: game_textures.sheet.clone(),
+                texture_atlas_image: UiTextureAtlasImage {
+                    index: game_textures.pickups.get(name).copied().unwrap_or_default(),
+                    ..Default::default()
+                },
+                ..Default::default()
+            });
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        width: Val::Px(HUD_POWER_UP_SIZE.w),
+                        height: Val::Px(3.),
+                        ..Default::default()
+                    },
+                    background_color: Color::rgba(0., 0., 0., 0.5).into(),
+                    ..Default::default()
+                })
+                .with_children(|parent| {
+                    parent
+                        .spawn(NodeBundle {
+                            style: Style {
+                                width: Val::Percent(100.),
+                                height: Val::Percent(100.),
+                                ..Default::default()
+                            },
+                            background_color: Color::WHITE.into(),
+                            ..Default::default()
+                        })
+                        .insert(HudPowerUpTimer(name.to_string()));
+                });
+        });
+    if stacks > 1 {
+        spawn_numerals(parent, hud_assets, stacks);
+    }
+}
@@ -0,0 +1,261 @@
+use crate::{
+    asset::meteor::MeteorField,
+    component::{
+        collider::{Collider, Layers},
+        damage::{Damage, HitFlash},
+        explosion::ExplosionKind,
+        health::Health,
+        laser::Laser,
+        meteor::Meteor,
+        movement::Movable,
+        player::{FromPlayer, Invulnerable, Player},
+        sprite::SpriteSize,
+        state::InGame,
+        velocity::Velocity,
+        weapon::Piercing,
+    },
+    event::{
+        collision::Collision,
+        player::{ComboChanged, ScoreChanged},
+    },
+    plugin::{
+        collision::{collision_detection_system, spawn_explosion},
+        pickup::pickup_drop_system,
+        state::GameplaySet,
+    },
+    resource::{
+        data::GameData,
+        global::{GameTextures, PlayerState},
+        pickup::PowerUps,
+        playfield::PlayField,
+        pool::{Release, SpritePool},
+        rng::GameRng,
+        wave::WaveDirector,
+    },
+    EXPLOSION_SIZE, SPRITE_SCALE,
+};
+use bevy::{prelude::*, utils::HashSet};
+use rand::prelude::*;
+use std::f32::consts::{FRAC_PI_2, TAU};
+
+pub struct MeteorPlugin;
+
+impl Plugin for MeteorPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            FixedUpdate,
+            (
+                meteor_spin_system.before(collision_detection_system),
+                (meteor_spawn_system, meteor_hit_system)
+                    // fixed order, after the other systems drawing from the GameRng
+                    .chain()
+                    .after(pickup_drop_system),
+            )
+                .in_set(GameplaySet),
+        );
+    }
+}
+
+/// Drop a meteor in from the top at the cadence of the current wave
+pub fn meteor_spawn_system(
+    mut commands: Commands,
+    game_data: Res<GameData>,
+    meteor_fields: Res<Assets<MeteorField>>,
+    game_textures: Res<GameTextures>,
+    playfield: Res<PlayField>,
+    mut wave_director: ResMut<WaveDirector>,
+    mut rng: ResMut<GameRng>,
+) {
+    let Some(meteor_field) = meteor_fields.get(&game_data.meteors) else {
+        return;
+    };
+    if !wave_director.take_meteor() {
+        return;
+    }
+    let kind = rng.gen_range(0..meteor_field.kinds.len());
+    let (min, max) = meteor_field.speed;
+    let speed = rng.gen_range(min..=max);
+    let drift = meteor_field.drift.to_radians();
+    let heading = -FRAC_PI_2 + rng.gen_range(-drift..=drift);
+    let x = rng.gen_range(-0.45..0.45) * playfield.w;
+    spawn_meteor(
+        &mut commands,
+        &game_textures,
+        meteor_field,
+        &mut rng,
+        (kind, 0),
+        Vec2::new(x, playfield.h / 2. + 50.),
+        Vec2::from_angle(heading) * speed,
+    );
+}
+
+fn spawn_meteor(
+    commands: &mut Commands,
+    game_textures: &GameTextures,
+    meteor_field: &MeteorField,
+    rng: &mut GameRng,
+    (kind, size): (usize, usize),
+    position: Vec2,
+    velocity: Vec2,
+) {
+    let def = &meteor_field.sizes[size];
+    let (sprite, sprite_size) = meteor_field.kinds[kind].sprite(size, rng.gen());
+    let (min, max) = meteor_field.spin;
+    let spin = rng.gen_range(min..=max).to_radians() * if rng.gen() { 1. } else { -1. };
+    let index = game_textures
+        .meteors
+        .get(sprite)
+        .copied()
+        .unwrap_or_default();
+    commands
+        .spawn(SpriteSheetBundle {
+            sprite: TextureAtlasSprite::new(index),
+            texture_atlas: game_textures.sheet.clone(),
+            transform: Transform {
+                translation: position.extend(9.),
+                rotation: Quat::from_rotation_z(rng.gen_range(0. ..TAU)),
+                scale: Vec3::new(SPRITE_SCALE, SPRITE_SCALE, 1.),
+            },
+            ..Default::default()
+        })
+        .insert(Meteor { kind, size, spin })
+        .insert(InGame)
+        .insert(sprite_size)
+        .insert(game_textures.hitbox(&def.hitbox, index))
+        .insert(Collider::new(Layers::HAZARD, Layers::PLAYER_PROJECTILE))
+        .insert(Health::new(def.hp))
+        .insert(HitFlash::default())
+        .insert(Damage(def.damage))
+        .insert(Movable { auto_despawn: true })
+        .insert(Velocity {
+            x: velocity.x,
+            y: velocity.y,
+        });
+}
+
+pub fn meteor_spin_system(time: Res<Time>, mut query: Query<(&mut Transform, &Meteor)>) {
+    for (mut transform, meteor) in query.iter_mut() {
+        transform.rotate_z(meteor.spin * time.delta_seconds());
+    }
+}
+
+/// Break the meteors shot down into pieces of the next size, and shatter those rammed into the ship
+///
+/// Only shot down meteors score, the damage to the ship is dealt by `plaer_laser_collision_system`.
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
+pub fn meteor_hit_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    game_data: Res<GameData>,
+    meteor_fields: Res<Assets<MeteorField>>,
+    game_textures: Res<GameTextures>,
+    mut player_state: ResMut<PlayerState>,
+    power_ups: Res<PowerUps>,
+    mut pool: ResMut<SpritePool>,
+    mut rng: ResMut<GameRng>,
+    mut collision_events: EventReader<Collision>,
+    mut score_events: EventWriter<ScoreChanged>,
+    mut combo_events: EventWriter<ComboChanged>,
+    mut meteor_query: Query<(
+        &Transform,
+        &Velocity,
+        &SpriteSize,
+        &mut Health,
+        &mut HitFlash,
+        &Meteor,
+    )>,
+    mut laser_query: Query<(&Damage, Option<&mut Piercing>), (With<Laser>, With<FromPlayer>)>,
+    player_query: Query<(), (With<Player>, Without<Invulnerable>)>,
+) {
+    let Some(meteor_field) = meteor_fields.get(&game_data.meteors) else {
+        return;
+    };
+    let mut despawn_set = HashSet::<Entity>::new();
+    let mut broken = Vec::new();
+    for collision in collision_events.read() {
+        // rammed, shattered without a score or pieces hitting the ship again
+        if player_query.contains(collision.entity) {
+            if let Ok((meteor_tf, _, size, ..)) = meteor_query.get(collision.other) {
+                if despawn_set.insert(collision.other) {
+                    spawn_explosion(
+                        &mut commands,
+                        &mut pool,
+                        &game_textures,
+                        meteor_tf.translation,
+                        ExplosionKind::Meteor,
+                        size.w / EXPLOSION_SIZE,
+                    );
+                }
+            }
+            continue;
+        }
+        let (Ok((meteor_tf, _, size, mut health, mut flash, meteor)), Ok((damage, piercing))) = (
+            meteor_query.get_mut(collision.entity),
+            laser_query.get_mut(collision.other),
+        ) else {
+            continue;
+        };
+        if despawn_set.contains(&collision.other) || despawn_set.contains(&collision.entity) {
+            continue;
+        }
+        match piercing {
+            Some(piercing) if piercing.hit.contains(&collision.entity) => continue,
+            Some(mut piercing) => piercing.hit.push(collision.entity),
+            None => {
+                despawn_set.insert(collision.other);
+            }
+        }
+        flash.hit();
+        if !health.damage(damage.0) {
+            continue;
+        }
+        despawn_set.insert(collision.entity);
+        broken.push(collision.entity);
+        let combo = player_state.combo;
+        let points = player_state.kill(
+            meteor_field.sizes[meteor.size].score * power_ups.score_multiplier(),
+            time.elapsed_seconds_f64(),
+        );
+        score_events.send(ScoreChanged {
+            score: player_state.score,
+            points,
+        });
+        if player_state.combo != combo {
+            combo_events.send(ComboChanged {
+                combo: player_state.combo,
+            });
+        }
+        spawn_explosion(
+            &mut commands,
+            &mut pool,
+            &game_textures,
+            meteor_tf.translation,
+            ExplosionKind::Meteor,
+            size.w / EXPLOSION_SIZE,
+        );
+    }
+    // pieces fly apart evenly, on top of the drift of their meteor
+    for entity in broken {
+        let Ok((transform, velocity, _, _, _, meteor)) = meteor_query.get(entity) else {
+            continue;
+        };
+        let size = meteor.size + 1;
+        let pieces = meteor_field.sizes[meteor.size].pieces;
+        let offset = rng.gen_range(0. ..TAU);
+        for piece in 0..pieces {
+            let direction = Vec2::from_angle(offset + TAU * piece as f32 / pieces as f32);
+            spawn_meteor(
+                &mut commands,
+                &game_textures,
+                meteor_field,
+                &mut rng,
+                (meteor.kind, size),
+                transform.translation.truncate() + direction * 8.,
+                Vec2::new(velocity.x, velocity.y) + direction * meteor_field.scatter,
+            );
+        }
+    }
+    despawn_set
+        .iter()
+        .for_each(|entity| commands.add(Release(*entity)));
+}
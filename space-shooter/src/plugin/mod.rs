@@ -1,4 +1,20 @@
+pub mod animation;
+pub mod boss;
 pub mod collision;
+pub mod data;
+pub mod debug;
+pub mod emitter;
 pub mod enemy;
+pub mod highscore;
+pub mod hud;
+pub mod meteor;
 pub mod movement;
+pub mod path;
+pub mod pickup;
 pub mod player;
+pub mod pool;
+pub mod replay;
+pub mod score;
+pub mod shield;
+pub mod state;
+pub mod wave;
@@ -0,0 +1,26 @@
+use crate::{component::path::PathFollower, plugin::state::GameplaySet};
+use bevy::prelude::*;
+
+pub struct PathPlugin;
+
+impl Plugin for PathPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(FixedUpdate, path_follow_system.in_set(GameplaySet));
+    }
+}
+
+pub fn path_follow_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut Transform, &mut PathFollower)>,
+) {
+    for (entity, mut transform, mut follower) in query.iter_mut() {
+        match follower.advance(time.delta_seconds()) {
+            Some(position) => {
+                let translation = &mut transform.translation;
+                (translation.x, translation.y) = (position.x, position.y);
+            }
+            None => commands.entity(entity).despawn(),
+        }
+    }
+}
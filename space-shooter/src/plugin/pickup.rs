@@ -0,0 +1,234 @@
+use crate::{
+    asset::pickup::{LootTable, PickupDef, PickupEffect},
+    component::{
+        collider::{Collider, Layers},
+        movement::Movable,
+        pickup::Pickup,
+        player::Player,
+        state::InGame,
+        velocity::Velocity,
+    },
+    event::{
+        collision::Collision,
+        pickup::{PowerUpCollected, PowerUpExpired},
+        player::{EnemyKilled, LivesChanged, PlayerKilled, ScoreChanged},
+    },
+    plugin::{
+        collision::{
+            collision_detection_system, enemy_laser_collision_system, plaer_laser_collision_system,
+        },
+        enemy::enemy_move_system,
+        state::{AppState, GameplaySet},
+    },
+    resource::{
+        config::windowed,
+        data::GameData,
+        global::{GameTextures, PlayerState},
+        pickup::PowerUps,
+        pool::{Release, SpritePool},
+        rng::GameRng,
+    },
+    PICKUP_SPEED, PLAYER_LIVES_MAX, SPRITE_SCALE,
+};
+use bevy::{prelude::*, utils::HashSet};
+use rand::prelude::*;
+
+pub struct PickupPlugin;
+
+impl Plugin for PickupPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<PowerUpCollected>()
+            .add_event::<PowerUpExpired>()
+            .init_resource::<PowerUps>()
+            .add_systems(OnEnter(AppState::MainMenu), power_up_reset_system)
+            .add_systems(Update, pickup_audio_system.run_if(windowed))
+            .add_systems(
+                FixedUpdate,
+                (
+                    pickup_drop_system
+                        // the kills of this tick, after the enemies drew from the GameRng
+                        .after(enemy_laser_collision_system)
+                        .after(enemy_move_system),
+                    // power-ups apply to the hits of the tick they are picked up in
+                    pickup_collect_system
+                        .after(collision_detection_system)
+                        .before(enemy_laser_collision_system)
+                        .before(plaer_laser_collision_system),
+                    (power_up_timer_system, power_up_loss_system)
+                        .chain()
+                        .after(plaer_laser_collision_system),
+                )
+                    .in_set(GameplaySet),
+            );
+    }
+}
+
+pub fn power_up_reset_system(mut power_ups: ResMut<PowerUps>) {
+    *power_ups = PowerUps::default();
+}
+
+/// Roll the loot table for every enemy destroyed
+#[allow(clippy::too_many_arguments)]
+pub fn pickup_drop_system(
+    mut commands: Commands,
+    game_data: Res<GameData>,
+    loot_tables: Res<Assets<LootTable>>,
+    game_textures: Res<GameTextures>,
+    mut pool: ResMut<SpritePool>,
+    mut rng: ResMut<GameRng>,
+    mut kill_events: EventReader<EnemyKilled>,
+) {
+    let Some(loot_table) = loot_tables.get(&game_data.pickups) else {
+        return;
+    };
+    for killed in kill_events.read() {
+        if !rng.gen_bool(loot_table.drop_chance as f64) {
+            continue;
+        }
+        let Ok(pickup) = loot_table
+            .pickups
+            .choose_weighted(&mut **rng, |pickup| pickup.weight)
+        else {
+            continue;
+        };
+        spawn_pickup(
+            &mut commands,
+            &mut pool,
+            &game_textures,
+            pickup,
+            killed.position,
+        );
+    }
+}
+
+fn spawn_pickup(
+    commands: &mut Commands,
+    pool: &mut SpritePool,
+    game_textures: &GameTextures,
+    pickup: &PickupDef,
+    position: Vec3,
+) {
+    pool.acquire(commands)
+        .insert(SpriteSheetBundle {
+            sprite: TextureAtlasSprite::new(
+                game_textures
+                    .pickups
+                    .get(&pickup.name)
+                    .copied()
+                    .unwrap_or_default(),
+            ),
+            texture_atlas: game_textures.sheet.clone(),
+            transform: Transform {
+                translation: position.truncate().extend(8.),
+                scale: Vec3::new(SPRITE_SCALE, SPRITE_SCALE, 1.),
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .insert(Pickup(pickup.clone()))
+        .insert(InGame)
+        .insert(pickup.sprite_size())
+        .insert(Collider::passive(Layers::PICKUP))
+        .insert(Movable { auto_despawn: true })
+        .insert(Velocity {
+            x: 0.,
+            y: -PICKUP_SPEED,
+        });
+}
+
+/// Apply the power-ups the player touches
+#[allow(clippy::too_many_arguments)]
+pub fn pickup_collect_system(
+    mut commands: Commands,
+    mut power_ups: ResMut<PowerUps>,
+    mut player_state: ResMut<PlayerState>,
+    mut collision_events: EventReader<Collision>,
+    mut collected_events: EventWriter<PowerUpCollected>,
+    mut lives_events: EventWriter<LivesChanged>,
+    mut score_events: EventWriter<ScoreChanged>,
+    player_query: Query<(), With<Player>>,
+    pickup_query: Query<&Pickup>,
+) {
+    let mut collected = HashSet::<Entity>::new();
+    for collision in collision_events.read() {
+        let (Ok(()), Ok(Pickup(pickup))) = (
+            player_query.get(collision.entity),
+            pickup_query.get(collision.other),
+        ) else {
+            continue;
+        };
+        if !collected.insert(collision.other) {
+            continue;
+        }
+        power_ups.grant(pickup);
+        match pickup.effect {
+            PickupEffect::ExtraLife => {
+                player_state.lives = (player_state.lives + 1).min(PLAYER_LIVES_MAX);
+                lives_events.send(LivesChanged {
+                    lives: player_state.lives,
+                });
+            }
+            PickupEffect::Points { points } => {
+                player_state.score += points;
+                score_events.send(ScoreChanged {
+                    score: player_state.score,
+                    points,
+                });
+            }
+            // timed effects are read from PowerUps, weapons are switched by the player plugin
+            // and shields charged by the shield plugin
+            _ => {}
+        }
+        collected_events.send(PowerUpCollected {
+            name: pickup.name.clone(),
+            effect: pickup.effect,
+        });
+    }
+    collected
+        .iter()
+        .for_each(|entity| commands.add(Release(*entity)));
+}
+
+/// Run the timed effects down
+pub fn power_up_timer_system(
+    time: Res<Time>,
+    mut power_ups: ResMut<PowerUps>,
+    mut expired_events: EventWriter<PowerUpExpired>,
+) {
+    if power_ups.active.is_empty() {
+        return;
+    }
+    for expired in power_ups.tick(time.delta_seconds()) {
+        expired_events.send(PowerUpExpired {
+            name: expired.name,
+            effect: expired.effect,
+        });
+    }
+}
+
+/// A destroyed ship loses every power-up
+pub fn power_up_loss_system(
+    mut power_ups: ResMut<PowerUps>,
+    mut killed_events: EventReader<PlayerKilled>,
+) {
+    if killed_events.read().last().is_some() {
+        power_ups.active.clear();
+    }
+}
+
+/// Shields play their own sound when charged, see `shield_audio_system`
+pub fn pickup_audio_system(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut collected_events: EventReader<PowerUpCollected>,
+) {
+    let collected = collected_events
+        .read()
+        .filter(|collected| !matches!(collected.effect, PickupEffect::Shield { .. }));
+    for _ in collected {
+        commands.spawn(AudioBundle {
+            source: asset_server.load("Bonus/sfx_zap.ogg"),
+            settings: PlaybackSettings::DESPAWN,
+        });
+    }
+}
@@ -1,24 +1,77 @@
 use crate::{
+    asset::{
+        pickup::PickupEffect,
+        weapon::{WeaponDef, WeaponKind, WeaponLibrary},
+    },
     component::{
+        animation::{Animation, AnimationMode},
+        collider::{Collider, Layers},
+        damage::{Damage, HitFlash},
+        emitter::Homing,
+        enemy::FromEnemy,
+        health::Health,
         laser::Laser,
         movement::Movable,
-        player::{FromPlayer, Player, PlayerFire},
+        player::{FromPlayer, Invulnerable, Player, PlayerDamage, PlayerFire},
+        shield::{Shield, ShieldOverlay},
+        state::InGame,
         velocity::Velocity,
+        weapon::{Arsenal, Piercing, Weapon},
+    },
+    event::{pickup::PowerUpCollected, player::WeaponChanged},
+    plugin::{
+        collision::plaer_laser_collision_system,
+        emitter::fan,
+        meteor::meteor_hit_system,
+        movement::movement_system,
+        pickup::{pickup_collect_system, power_up_loss_system, power_up_timer_system},
+        state::{AppState, GameplaySet},
     },
-    resource::global::{GameTextures, PlayerState},
-    PLAYER_LASER_SIZE, PLAYER_SIZE, SPRITE_SCALE,
+    resource::{
+        config::{windowed, GameConfig},
+        data::GameData,
+        global::{GameTextures, PlayerState},
+        pickup::PowerUps,
+        playfield::PlayField,
+        pool::{Release, SpritePool},
+    },
+    PLAYER_BLINK_RATE, PLAYER_ENGINE_FPS, PLAYER_HITBOX, PLAYER_HP, PLAYER_MUZZLE_WIDTH,
+    PLAYER_RESPAWN_DELAY, PLAYER_SHIELD_HITS, PLAYER_SHIELD_RECHARGE, PLAYER_SIZE,
+    PLAYER_SPAWN_CLEAR_RADIUS, SPRITE_SCALE,
 };
-use bevy::{audio::Volume, prelude::*, window::PrimaryWindow};
+use bevy::{audio::Volume, prelude::*};
+use std::f32::consts::FRAC_PI_2;
 
 pub struct PlayerPlugin;
 
 impl Plugin for PlayerPlugin {
     fn build(&self, app: &mut App) {
-        app.insert_resource(PlayerState::default())
-            .add_systems(Startup, player_audio_spawn_system)
-            .add_systems(Update, player_spawn_system)
-            .add_systems(Update, player_fire_system)
-            .add_systems(Update, player_move_system);
+        app.add_event::<WeaponChanged>()
+            .insert_resource(PlayerState::default())
+            .add_systems(Startup, player_audio_spawn_system.run_if(windowed))
+            .add_systems(OnEnter(AppState::MainMenu), player_reset_system)
+            .add_systems(
+                FixedUpdate,
+                (
+                    player_spawn_system,
+                    player_weapon_system
+                        .after(pickup_collect_system)
+                        .after(power_up_timer_system)
+                        .after(power_up_loss_system),
+                    // shots leave from where the ship is after this tick's move
+                    player_fire_system
+                        .after(player_weapon_system)
+                        .after(movement_system),
+                    player_move_system.before(movement_system),
+                    player_damage_system,
+                    // the tick invulnerability runs out on is the same in every run
+                    player_invulnerability_system
+                        .after(player_spawn_system)
+                        .before(plaer_laser_collision_system)
+                        .before(meteor_hit_system),
+                )
+                    .in_set(GameplaySet),
+            );
     }
 }
 
@@ -32,99 +85,382 @@ pub fn player_audio_spawn_system(mut commands: Commands, asset_server: Res<Asset
     ));
 }
 
+pub fn player_reset_system(mut player_state: ResMut<PlayerState>) {
+    *player_state = PlayerState::default();
+}
+
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
 pub fn player_spawn_system(
     mut commands: Commands,
     time: Res<Time>,
+    config: Res<GameConfig>,
     mut player_state: ResMut<PlayerState>,
     game_textures: Res<GameTextures>,
-    mut win_query: Query<&mut Window, With<PrimaryWindow>>,
+    playfield: Res<PlayField>,
+    game_data: Res<GameData>,
+    weapon_libraries: Res<Assets<WeaponLibrary>>,
+    power_ups: Res<PowerUps>,
+    mut weapon_events: EventWriter<WeaponChanged>,
+    // boss beams have no velocity, they are left alone
+    bullet_query: Query<(Entity, &Transform), (With<Laser>, With<FromEnemy>, With<Velocity>)>,
 ) {
-    let mut spawnable = !player_state.alive;
+    let mut spawnable = !player_state.alive && !player_state.game_over();
     if let Some(last_shot) = player_state.last_shot {
         let now = time.elapsed_seconds_f64();
-        if now < last_shot + 3. {
+        if now < last_shot + PLAYER_RESPAWN_DELAY {
             spawnable = false;
         }
     }
+    // every ship starts with the first weapon of the library
+    let Some(weapon) = weapon_libraries
+        .get(&game_data.weapons)
+        .and_then(|weapons| weapons.weapons.first())
+    else {
+        return;
+    };
     if spawnable {
-        let win = win_query.single_mut();
-        let (_ww, wh) = (win.resolution.width(), win.resolution.height());
-        let bottom = -wh / 2.;
-        commands
-            .spawn(SpriteBundle {
-                texture: game_textures.player.clone(),
-                transform: Transform {
-                    translation: Vec3::new(
-                        0.,
-                        bottom + PLAYER_SIZE.h / 2. * SPRITE_SCALE + 10.,
-                        10.,
-                    ),
-                    scale: Vec3::new(SPRITE_SCALE, SPRITE_SCALE, 1.),
-                    ..Default::default()
-                },
+        let level = power_ups.weapon_level();
+        let bottom = -playfield.h / 2.;
+        let position = Vec2::new(0., bottom + PLAYER_SIZE.h / 2. * SPRITE_SCALE + 10.);
+        let mut player = commands.spawn(SpriteSheetBundle {
+            sprite: TextureAtlasSprite::new(game_textures.player),
+            texture_atlas: game_textures.sheet.clone(),
+            transform: Transform {
+                translation: position.extend(10.),
+                scale: Vec3::new(SPRITE_SCALE, SPRITE_SCALE, 1.),
                 ..Default::default()
-            })
+            },
+            ..Default::default()
+        });
+        player
             .insert(Player)
+            .insert(InGame)
             .insert(PLAYER_SIZE)
+            .insert(game_textures.hitbox(&PLAYER_HITBOX, game_textures.player))
+            .insert(Collider::new(
+                Layers::PLAYER,
+                Layers::ENEMY_PROJECTILE | Layers::PICKUP | Layers::HAZARD,
+            ))
             .insert(Movable {
                 auto_despawn: false,
             })
-            .insert(Velocity::default());
+            .insert(Velocity::default())
+            .insert(Health::new(PLAYER_HP))
+            .insert(HitFlash::default())
+            .insert(Weapon::new(weapon, level))
+            .insert(Arsenal(vec![weapon.kind]))
+            .insert(Shield::new(PLAYER_SHIELD_HITS, PLAYER_SHIELD_RECHARGE))
+            .with_children(|parent| {
+                parent
+                    .spawn(SpriteSheetBundle {
+                        sprite: TextureAtlasSprite::new(
+                            game_textures
+                                .player_damage
+                                .first()
+                                .copied()
+                                .unwrap_or_default(),
+                        ),
+                        texture_atlas: game_textures.sheet.clone(),
+                        transform: Transform::from_xyz(0., 0., 0.5),
+                        visibility: Visibility::Hidden,
+                        ..Default::default()
+                    })
+                    .insert(PlayerDamage);
+                parent
+                    .spawn(SpriteSheetBundle {
+                        sprite: TextureAtlasSprite::new(
+                            game_textures
+                                .player_shield
+                                .first()
+                                .copied()
+                                .unwrap_or_default(),
+                        ),
+                        texture_atlas: game_textures.sheet.clone(),
+                        transform: Transform::from_xyz(0., 0., 1.),
+                        visibility: Visibility::Hidden,
+                        ..Default::default()
+                    })
+                    .insert(ShieldOverlay);
+                parent
+                    .spawn(SpriteSheetBundle {
+                        sprite: TextureAtlasSprite::new(
+                            game_textures
+                                .player_engine
+                                .first()
+                                .copied()
+                                .unwrap_or_default(),
+                        ),
+                        texture_atlas: game_textures.sheet.clone(),
+                        // flame under the engine, behind the ship
+                        transform: Transform::from_xyz(0., -PLAYER_SIZE.h / 2. - 12., -0.5),
+                        ..Default::default()
+                    })
+                    .insert(Animation::new(
+                        game_textures.player_engine.clone(),
+                        PLAYER_ENGINE_FPS,
+                        AnimationMode::Loop,
+                    ));
+            });
+        // a moment to find its bearings, away from the bullets already flying
+        if config.invulnerability > 0. {
+            player.insert(Invulnerable {
+                remaining: config.invulnerability,
+            });
+        }
+        for (bullet, bullet_tf) in bullet_query.iter() {
+            if bullet_tf.translation.truncate().distance(position) < PLAYER_SPAWN_CLEAR_RADIUS {
+                commands.add(Release(bullet));
+            }
+        }
         player_state.spawn();
+        weapon_events.send(WeaponChanged {
+            name: weapon.name.clone(),
+            level,
+        });
     }
 }
 
+/// Switch weapons on pickups and on the cycle key, and follow the weapon level power-ups
+#[allow(clippy::too_many_arguments)]
+pub fn player_weapon_system(
+    // `just_pressed` is per frame, track the key edge per tick instead
+    mut cycle_held: Local<bool>,
+    kb: Res<Input<KeyCode>>,
+    game_data: Res<GameData>,
+    weapon_libraries: Res<Assets<WeaponLibrary>>,
+    power_ups: Res<PowerUps>,
+    mut collected_events: EventReader<PowerUpCollected>,
+    mut weapon_events: EventWriter<WeaponChanged>,
+    mut query: Query<(&mut Weapon, &mut Arsenal), With<Player>>,
+) {
+    let cycle_pressed = kb.pressed(KeyCode::X) && !*cycle_held;
+    *cycle_held = kb.pressed(KeyCode::X);
+    let (Some(weapons), Ok((mut weapon, mut arsenal))) = (
+        weapon_libraries.get(&game_data.weapons),
+        query.get_single_mut(),
+    ) else {
+        return;
+    };
+    let mut kind = weapon.kind;
+    for collected in collected_events.read() {
+        if let PickupEffect::Arm { weapon } = collected.effect {
+            if !arsenal.0.contains(&weapon) {
+                arsenal.0.push(weapon);
+            }
+            kind = weapon;
+        }
+    }
+    if cycle_pressed {
+        let index = arsenal
+            .0
+            .iter()
+            .position(|armed| *armed == kind)
+            .unwrap_or(0);
+        kind = arsenal.0[(index + 1) % arsenal.0.len()];
+    }
+    let level = power_ups.weapon_level();
+    if kind == weapon.kind && level == weapon.level {
+        return;
+    }
+    let Some(def) = weapons.get(kind) else {
+        return;
+    };
+    // switching does not skip the fire cooldown
+    weapon.kind = kind;
+    weapon.level = level;
+    weapon.stats = def.stats(level);
+    weapon_events.send(WeaponChanged {
+        name: def.name.clone(),
+        level,
+    });
+}
+
+/// Fire the weapon while Space is held, one shot per fire rate interval
+#[allow(clippy::too_many_arguments)]
 pub fn player_fire_system(
     mut commands: Commands,
+    time: Res<Time>,
     kb: Res<Input<KeyCode>>,
+    game_data: Res<GameData>,
+    weapon_libraries: Res<Assets<WeaponLibrary>>,
     game_textures: Res<GameTextures>,
+    mut pool: ResMut<SpritePool>,
     query_audio: Query<&AudioSink, With<PlayerFire>>,
-    player_query: Query<&mut Transform, With<Player>>,
+    mut player_query: Query<(&Transform, &mut Weapon), With<Player>>,
 ) {
-    if let Ok(player_tf) = player_query.get_single() {
-        if kb.just_pressed(KeyCode::Space) {
-            let (player_w, player_h) = (player_tf.translation.x, player_tf.translation.y);
-            let mut spawn_laser = |muzzle_offset| {
-                if let Ok(sink) = query_audio.get_single() {
-                    sink.play();
-                }
-                commands
-                    .spawn(SpriteBundle {
-                        texture: game_textures.player_laser.clone(),
-                        transform: Transform {
-                            translation: Vec3::new(player_w + muzzle_offset, player_h, 9.),
-                            scale: Vec3::new(SPRITE_SCALE, SPRITE_SCALE, 1.),
-                            ..Default::default()
-                        },
-                        ..Default::default()
-                    })
-                    .insert(Laser)
-                    .insert(FromPlayer)
-                    .insert(PLAYER_LASER_SIZE)
-                    .insert(Movable { auto_despawn: true })
-                    .insert(Velocity { x: 0., y: 5. });
+    let Ok((player_tf, mut weapon)) = player_query.get_single_mut() else {
+        return;
+    };
+    if weapon.cooldown > 0. {
+        weapon.cooldown -= time.delta_seconds();
+    }
+    if !kb.pressed(KeyCode::Space) || weapon.cooldown > 0. {
+        return;
+    }
+    let Some(def) = weapon_libraries
+        .get(&game_data.weapons)
+        .and_then(|weapons| weapons.get(weapon.kind))
+    else {
+        return;
+    };
+    weapon.cooldown += 1. / weapon.stats.fire_rate;
+    if let Ok(sink) = query_audio.get_single() {
+        sink.play();
+    }
+    let origin = player_tf.translation.truncate();
+    for (muzzle_offset, heading) in volley(&weapon) {
+        spawn_projectile(
+            &mut commands,
+            &mut pool,
+            &game_textures,
+            def,
+            &weapon,
+            origin + Vec2::new(muzzle_offset, 0.),
+            heading,
+        );
+    }
+}
+
+/// Muzzle offset and heading of every projectile of one shot
+fn volley(weapon: &Weapon) -> Vec<(f32, f32)> {
+    let count = weapon.stats.spread_count;
+    if weapon.stats.spread > 0. {
+        return fan(FRAC_PI_2, count, weapon.stats.spread)
+            .into_iter()
+            .map(|heading| (0., heading))
+            .collect();
+    }
+    // side by side over the muzzle width
+    (0..count)
+        .map(|index| {
+            let fraction = if count > 1 {
+                index as f32 / (count - 1) as f32 - 0.5
+            } else {
+                0.
             };
-            spawn_laser(-23.);
-            spawn_laser(23.);
+            (PLAYER_MUZZLE_WIDTH * fraction, FRAC_PI_2)
+        })
+        .collect()
+}
+
+fn spawn_projectile(
+    commands: &mut Commands,
+    pool: &mut SpritePool,
+    game_textures: &GameTextures,
+    def: &WeaponDef,
+    weapon: &Weapon,
+    position: Vec2,
+    heading: f32,
+) {
+    let velocity = Vec2::from_angle(heading) * weapon.stats.speed;
+    let sprite = game_textures
+        .weapons
+        .get(&def.kind)
+        .copied()
+        .unwrap_or_default();
+    let mut projectile = pool.acquire(commands);
+    projectile
+        .insert(SpriteSheetBundle {
+            sprite: TextureAtlasSprite::new(sprite),
+            texture_atlas: game_textures.sheet.clone(),
+            transform: Transform {
+                translation: position.extend(9.),
+                scale: Vec3::new(SPRITE_SCALE, SPRITE_SCALE, 1.),
+                // sprites point up
+                rotation: Quat::from_rotation_z(heading - FRAC_PI_2),
+            },
+            ..Default::default()
+        })
+        .insert(Laser)
+        .insert(FromPlayer)
+        .insert(InGame)
+        .insert(def.sprite_size())
+        .insert(game_textures.hitbox(&def.hitbox, sprite))
+        .insert(Collider::passive(Layers::PLAYER_PROJECTILE))
+        .insert(Damage(weapon.stats.damage))
+        .insert(Movable { auto_despawn: true })
+        .insert(Velocity {
+            x: velocity.x,
+            y: velocity.y,
+        });
+    match weapon.kind {
+        WeaponKind::Beam => {
+            projectile.insert(Piercing::default());
+        }
+        WeaponKind::Missiles => {
+            // missiles keep homing until they leave the playfield
+            projectile.insert(Homing {
+                turn_rate: weapon.stats.turn_rate.to_radians(),
+                remaining: f32::INFINITY,
+            });
         }
+        WeaponKind::Laser | WeaponKind::Spread => {}
     }
 }
 
 pub fn player_move_system(kb: Res<Input<KeyCode>>, mut query: Query<&mut Velocity, With<Player>>) {
     if let Ok(mut velocity) = query.get_single_mut() {
         velocity.x = if kb.pressed(KeyCode::Left) {
-            -5.
+            -300.
         } else if kb.pressed(KeyCode::Right) {
-            5.
+            300.
         } else {
             0.
         };
         velocity.y = if kb.pressed(KeyCode::Up) {
-            5.
+            300.
         } else if kb.pressed(KeyCode::Down) {
-            -5.
+            -300.
         } else {
             0.
         };
     }
 }
+
+/// Show the damage overlay matching the hit points the player has lost
+#[allow(clippy::type_complexity)]
+pub fn player_damage_system(
+    game_textures: Res<GameTextures>,
+    player_query: Query<(&Health, &Children), (With<Player>, Changed<Health>)>,
+    mut overlay_query: Query<(&mut TextureAtlasSprite, &mut Visibility), With<PlayerDamage>>,
+) {
+    for (health, children) in player_query.iter() {
+        let lost = health.max - health.hp;
+        // spread the overlays over the hits that do not destroy the ship
+        let stages = game_textures.player_damage.len() as u32;
+        let hits = health.max.saturating_sub(1).max(1);
+        let stage = (lost * stages + hits - 1) / hits;
+        for child in children.iter() {
+            let Ok((mut sprite, mut visibility)) = overlay_query.get_mut(*child) else {
+                continue;
+            };
+            if stage == 0 {
+                *visibility = Visibility::Hidden;
+            } else {
+                sprite.index = game_textures.player_damage[(stage.min(stages) - 1) as usize];
+                *visibility = Visibility::Inherited;
+            }
+        }
+    }
+}
+
+/// Blink the respawned ship until its invulnerability runs out
+pub fn player_invulnerability_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut Invulnerable, &mut Visibility), With<Player>>,
+) {
+    for (entity, mut invulnerable, mut visibility) in query.iter_mut() {
+        invulnerable.remaining -= time.delta_seconds();
+        let over = invulnerable.remaining <= 0.;
+        if over {
+            commands.entity(entity).remove::<Invulnerable>();
+        }
+        let shown = (invulnerable.remaining * PLAYER_BLINK_RATE) as u32 % 2 == 0;
+        *visibility = if over || shown {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+    }
+}
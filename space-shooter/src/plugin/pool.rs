@@ -0,0 +1,25 @@
+use crate::{component::pool::Pooled, resource::pool::SpritePool, SPRITE_POOL_PREWARM};
+use bevy::prelude::*;
+
+pub struct PoolPlugin;
+
+impl Plugin for PoolPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SpritePool>()
+            .add_systems(Startup, pool_prewarm_system);
+    }
+}
+
+/// Fill the pool up front, so the first waves do not allocate entities
+pub fn pool_prewarm_system(mut commands: Commands, mut pool: ResMut<SpritePool>) {
+    for _ in 0..SPRITE_POOL_PREWARM {
+        let entity = commands
+            .spawn(SpriteSheetBundle {
+                visibility: Visibility::Hidden,
+                ..Default::default()
+            })
+            .insert(Pooled { active: false })
+            .id();
+        pool.free.push(entity);
+    }
+}
@@ -0,0 +1,137 @@
+use crate::{
+    component::state::InGame,
+    plugin::{
+        player::{player_fire_system, player_move_system, player_weapon_system},
+        state::{AppState, GameplaySet},
+    },
+    resource::{
+        global::PlayerState,
+        replay::{tick_mask, ReplayOutcome, ReplayPlayback, ReplayRecorder, REPLAY_KEYS},
+    },
+};
+use bevy::{app::AppExit, prelude::*};
+
+pub struct ReplayPlugin;
+
+impl Plugin for ReplayPlugin {
+    fn build(&self, app: &mut App) {
+        // input is captured and replayed per gameplay tick, ahead of the systems reading it
+        app.add_systems(
+            FixedUpdate,
+            (
+                replay_playback_system.run_if(resource_exists::<ReplayPlayback>()),
+                replay_record_system.run_if(resource_exists::<ReplayRecorder>()),
+            )
+                .chain()
+                .in_set(GameplaySet)
+                .before(player_move_system)
+                .before(player_weapon_system)
+                .before(player_fire_system),
+        )
+        // outside GameplaySet, so a replay ending in game over is still checked
+        .add_systems(
+            FixedUpdate,
+            replay_finish_system
+                .run_if(resource_exists::<ReplayPlayback>())
+                .before(replay_playback_system),
+        )
+        // a recording covers one game, it is saved at game over, on quitting to the menu or on exit
+        .add_systems(
+            OnEnter(AppState::GameOver),
+            replay_save_system.run_if(resource_exists::<ReplayRecorder>()),
+        )
+        .add_systems(
+            OnTransition {
+                from: AppState::Paused,
+                to: AppState::MainMenu,
+            },
+            replay_save_system.run_if(resource_exists::<ReplayRecorder>()),
+        )
+        .add_systems(
+            Last,
+            replay_save_system
+                .run_if(resource_exists::<ReplayRecorder>())
+                .run_if(on_event::<AppExit>()),
+        );
+    }
+}
+
+/// Overwrite the replay keys of `Input<KeyCode>` with the next recorded tick
+pub fn replay_playback_system(
+    mut playback: ResMut<ReplayPlayback>,
+    mut kb: ResMut<Input<KeyCode>>,
+) {
+    let Some(mask) = playback.next_tick() else {
+        return;
+    };
+    for (i, key) in REPLAY_KEYS.into_iter().enumerate() {
+        let was_pressed = playback.previous & 1 << i != 0;
+        let pressed = mask & 1 << i != 0;
+        // rebuild the key state so just_pressed/just_released match the recording
+        kb.reset(key);
+        if was_pressed {
+            kb.press(key);
+            kb.clear_just_pressed(key);
+        }
+        if pressed {
+            kb.press(key);
+        } else {
+            kb.release(key);
+        }
+    }
+    playback.previous = mask;
+}
+
+pub fn replay_record_system(mut recorder: ResMut<ReplayRecorder>, kb: Res<Input<KeyCode>>) {
+    if recorder.replay.outcome.is_none() {
+        recorder.replay.ticks.push(tick_mask(&kb));
+    }
+}
+
+/// Compare the end state with the recorded one and exit once every tick is played
+pub fn replay_finish_system(
+    mut reported: Local<bool>,
+    playback: Res<ReplayPlayback>,
+    player_state: Res<PlayerState>,
+    entity_query: Query<(), With<InGame>>,
+    mut exit: EventWriter<AppExit>,
+) {
+    if !playback.finished() || *reported {
+        return;
+    }
+    *reported = true;
+    let outcome = ReplayOutcome {
+        entities: entity_query.iter().len() as u32,
+        score: player_state.score,
+    };
+    match playback.replay.outcome {
+        Some(expected) if expected != outcome => {
+            warn!("replay diverged: expected {expected:?}, got {outcome:?}")
+        }
+        Some(_) => info!("replay matched: {outcome:?}"),
+        None => info!("replay finished: {outcome:?}"),
+    }
+    exit.send(AppExit);
+}
+
+pub fn replay_save_system(
+    mut recorder: ResMut<ReplayRecorder>,
+    player_state: Res<PlayerState>,
+    entity_query: Query<(), With<InGame>>,
+) {
+    if recorder.replay.outcome.is_some() {
+        return;
+    }
+    recorder.replay.outcome = Some(ReplayOutcome {
+        entities: entity_query.iter().len() as u32,
+        score: player_state.score,
+    });
+    match recorder.replay.save(&recorder.path) {
+        Ok(()) => info!(
+            "replay saved to {} ({} ticks)",
+            recorder.path.display(),
+            recorder.replay.ticks.len()
+        ),
+        Err(err) => error!("{err}"),
+    }
+}
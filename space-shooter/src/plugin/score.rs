@@ -0,0 +1,55 @@
+use crate::{
+    event::player::{
+        ComboChanged, EnemyKilled, GameOver, LivesChanged, PlayerKilled, ScoreChanged,
+    },
+    plugin::{
+        collision::plaer_laser_collision_system,
+        state::{AppState, GameplaySet},
+    },
+    resource::global::PlayerState,
+};
+use bevy::prelude::*;
+
+pub struct ScorePlugin;
+
+impl Plugin for ScorePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<EnemyKilled>()
+            .add_event::<ScoreChanged>()
+            .add_event::<ComboChanged>()
+            .add_event::<LivesChanged>()
+            .add_event::<PlayerKilled>()
+            .add_event::<GameOver>()
+            .add_systems(
+                FixedUpdate,
+                (
+                    combo_timeout_system,
+                    // within the tick of the last ship lost, the event is gone by the next frame
+                    game_over_system.after(plaer_laser_collision_system),
+                )
+                    .in_set(GameplaySet),
+            );
+    }
+}
+
+/// Drop the combo multiplier once no enemy was killed within `COMBO_WINDOW`
+pub fn combo_timeout_system(
+    time: Res<Time>,
+    mut player_state: ResMut<PlayerState>,
+    mut combo_events: EventWriter<ComboChanged>,
+) {
+    if player_state.combo > 1 && !player_state.combo_active(time.elapsed_seconds_f64()) {
+        player_state.combo = 1;
+        combo_events.send(ComboChanged { combo: 1 });
+    }
+}
+
+pub fn game_over_system(
+    mut game_over_events: EventReader<GameOver>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    if let Some(game_over) = game_over_events.read().last() {
+        info!("game over, score {}", game_over.score);
+        next_state.set(AppState::GameOver);
+    }
+}
@@ -0,0 +1,98 @@
+use crate::{
+    asset::pickup::PickupEffect,
+    component::{
+        player::Player,
+        shield::{Shield, ShieldOverlay},
+    },
+    event::{
+        pickup::PowerUpCollected,
+        player::{ShieldDown, ShieldUp},
+    },
+    plugin::{
+        collision::plaer_laser_collision_system, pickup::pickup_collect_system, state::GameplaySet,
+    },
+    resource::{config::windowed, global::GameTextures},
+};
+use bevy::prelude::*;
+
+pub struct ShieldPlugin;
+
+impl Plugin for ShieldPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<ShieldUp>()
+            .add_event::<ShieldDown>()
+            .add_systems(Update, shield_audio_system.run_if(windowed))
+            .add_systems(
+                FixedUpdate,
+                (
+                    // charged before the hits of the tick
+                    shield_charge_system
+                        .after(pickup_collect_system)
+                        .before(plaer_laser_collision_system),
+                    shield_overlay_system.after(plaer_laser_collision_system),
+                )
+                    .in_set(GameplaySet),
+            );
+    }
+}
+
+/// Charge the ship shield from pickups and its recharge
+pub fn shield_charge_system(
+    time: Res<Time>,
+    mut collected_events: EventReader<PowerUpCollected>,
+    mut up_events: EventWriter<ShieldUp>,
+    mut query: Query<&mut Shield, With<Player>>,
+) {
+    let Ok(mut shield) = query.get_single_mut() else {
+        return;
+    };
+    let mut gained = shield.recharge(time.delta_seconds());
+    for collected in collected_events.read() {
+        if let PickupEffect::Shield { hits } = collected.effect {
+            gained += shield.charge(hits);
+        }
+    }
+    if gained > 0 {
+        up_events.send(ShieldUp { hits: shield.hits });
+    }
+}
+
+/// Show the shield overlay matching the hits the shield absorbs
+pub fn shield_overlay_system(
+    game_textures: Res<GameTextures>,
+    player_query: Query<(&Shield, &Children), Changed<Shield>>,
+    mut overlay_query: Query<(&mut TextureAtlasSprite, &mut Visibility), With<ShieldOverlay>>,
+) {
+    for (shield, children) in player_query.iter() {
+        let stages = game_textures.player_shield.len();
+        for child in children.iter() {
+            let Ok((mut sprite, mut visibility)) = overlay_query.get_mut(*child) else {
+                continue;
+            };
+            if shield.hits == 0 || stages == 0 {
+                *visibility = Visibility::Hidden;
+            } else {
+                sprite.index = game_textures.player_shield[(shield.hits as usize).min(stages) - 1];
+                *visibility = Visibility::Inherited;
+            }
+        }
+    }
+}
+
+pub fn shield_audio_system(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut up_events: EventReader<ShieldUp>,
+    mut down_events: EventReader<ShieldDown>,
+) {
+    let sounds = up_events
+        .read()
+        .map(|_| "Bonus/sfx_shieldUp.ogg")
+        .chain(down_events.read().map(|_| "Bonus/sfx_shieldDown.ogg"));
+    for sound in sounds {
+        commands.spawn(AudioBundle {
+            source: asset_server.load(sound),
+            settings: PlaybackSettings::DESPAWN,
+        });
+    }
+}
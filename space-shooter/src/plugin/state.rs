@@ -0,0 +1,137 @@
+use crate::{
+    component::state::{InGame, StateScreen},
+    resource::{
+        config::{windowed, GameConfig},
+        rng::GameRng,
+    },
+};
+use bevy::prelude::*;
+
+#[derive(States, Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum AppState {
+    /// waiting for the data assets
+    #[default]
+    Loading,
+    MainMenu,
+    Playing,
+    Paused,
+    GameOver,
+}
+
+/// System set - gameplay systems in `FixedUpdate`, only run while `AppState::Playing`
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GameplaySet;
+
+pub struct StatePlugin;
+
+impl Plugin for StatePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_state::<AppState>()
+            .configure_sets(FixedUpdate, GameplaySet.run_if(in_state(AppState::Playing)))
+            .add_systems(
+                OnEnter(AppState::MainMenu),
+                (game_cleanup_system, auto_start_system.run_if(auto_start)),
+            )
+            .add_systems(
+                OnTransition {
+                    from: AppState::MainMenu,
+                    to: AppState::Playing,
+                },
+                game_start_system,
+            )
+            .add_systems(OnEnter(AppState::Paused), pause_system)
+            .add_systems(OnExit(AppState::Paused), resume_system)
+            .add_systems(Update, state_input_system.run_if(windowed));
+
+        for (state, message) in [
+            (AppState::MainMenu, "SPACE SHOOTER\n\npress Enter to start"),
+            (AppState::Paused, "PAUSED\n\nEsc to resume, Q to quit"),
+            (AppState::GameOver, "GAME OVER\n\npress Enter"),
+        ] {
+            app.add_systems(
+                OnEnter(state),
+                (move |commands: Commands| spawn_screen(commands, message)).run_if(windowed),
+            )
+            .add_systems(OnExit(state), screen_despawn_system);
+        }
+    }
+}
+
+/// Run condition - nobody is there to press Enter, go straight into the game
+fn auto_start(config: Res<GameConfig>) -> bool {
+    config.headless || config.replay.is_some()
+}
+
+pub fn auto_start_system(mut next_state: ResMut<NextState<AppState>>) {
+    next_state.set(AppState::Playing);
+}
+
+pub fn state_input_system(
+    kb: Res<Input<KeyCode>>,
+    state: Res<State<AppState>>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    let next = match state.get() {
+        AppState::MainMenu if kb.just_pressed(KeyCode::Return) => AppState::Playing,
+        AppState::Playing if kb.just_pressed(KeyCode::Escape) => AppState::Paused,
+        AppState::Paused if kb.just_pressed(KeyCode::Escape) => AppState::Playing,
+        AppState::Paused if kb.just_pressed(KeyCode::Q) => AppState::MainMenu,
+        AppState::GameOver if kb.just_pressed(KeyCode::Return) => AppState::MainMenu,
+        _ => return,
+    };
+    next_state.set(next);
+}
+
+/// Despawn everything left from the previous game, so a new one starts from a clean world
+pub fn game_cleanup_system(mut commands: Commands, query: Query<Entity, With<InGame>>) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+/// Every game starts from the seed, not from where the previous game left the GameRng
+pub fn game_start_system(mut rng: ResMut<GameRng>) {
+    rng.reseed();
+}
+
+pub fn pause_system(mut time: ResMut<Time<Virtual>>) {
+    time.pause();
+}
+
+pub fn resume_system(mut time: ResMut<Time<Virtual>>) {
+    time.unpause();
+}
+
+fn spawn_screen(mut commands: Commands, message: &str) {
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                width: Val::Percent(100.),
+                height: Val::Percent(100.),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .insert(StateScreen)
+        .with_children(|parent| {
+            parent.spawn(
+                TextBundle::from_section(
+                    message,
+                    TextStyle {
+                        font_size: 32.,
+                        color: Color::WHITE,
+                        ..Default::default()
+                    },
+                )
+                .with_text_alignment(TextAlignment::Center),
+            );
+        });
+}
+
+pub fn screen_despawn_system(mut commands: Commands, query: Query<Entity, With<StateScreen>>) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
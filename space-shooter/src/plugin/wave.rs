@@ -0,0 +1,91 @@
+use crate::{
+    asset::wave::{WaveClear, WaveScript},
+    component::enemy::Enemy,
+    event::wave::{WaveCleared, WaveStarted},
+    plugin::{
+        enemy::enemy_spawn_system,
+        state::{AppState, GameplaySet},
+    },
+    resource::{
+        data::GameData,
+        wave::{WaveDirector, WavePhase},
+    },
+};
+use bevy::prelude::*;
+
+pub struct WavePlugin;
+
+impl Plugin for WavePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<WaveStarted>()
+            .add_event::<WaveCleared>()
+            .init_resource::<WaveDirector>()
+            .add_systems(OnEnter(AppState::MainMenu), wave_reset_system)
+            .add_systems(
+                FixedUpdate,
+                wave_director_system
+                    .before(enemy_spawn_system)
+                    .in_set(GameplaySet),
+            );
+    }
+}
+
+pub fn wave_reset_system(mut wave_director: ResMut<WaveDirector>) {
+    *wave_director = WaveDirector::default();
+}
+
+/// Start the next wave after the intermission, and clear the current one once its condition holds
+pub fn wave_director_system(
+    time: Res<Time>,
+    game_data: Res<GameData>,
+    wave_scripts: Res<Assets<WaveScript>>,
+    mut wave_director: ResMut<WaveDirector>,
+    mut started_events: EventWriter<WaveStarted>,
+    mut cleared_events: EventWriter<WaveCleared>,
+    enemy_query: Query<(), With<Enemy>>,
+) {
+    let Some(wave_script) = wave_scripts.get(&game_data.waves) else {
+        return;
+    };
+    let time_step = time.delta_seconds();
+    wave_director.elapsed += time_step;
+    wave_director.next_spawn -= time_step;
+    wave_director.next_meteor -= time_step;
+
+    match wave_director.phase {
+        WavePhase::Intermission => {
+            if wave_director.elapsed < wave_script.intermission {
+                return;
+            }
+            let wave = wave_director.wave + 1;
+            let Some(def) = wave_script.wave(wave) else {
+                return;
+            };
+            match &def.boss {
+                Some(boss) => info!("wave {wave} started: boss {boss}"),
+                None => info!(
+                    "wave {wave} started: {} formations every {:.2}s",
+                    def.formations, def.cadence
+                ),
+            }
+            wave_director.start(wave, def);
+            started_events.send(WaveStarted { wave });
+        }
+        WavePhase::Active => {
+            let Some(def) = &wave_director.def else {
+                return;
+            };
+            let destroyed = wave_director.all_spawned() && enemy_query.is_empty();
+            let cleared = match def.clear {
+                WaveClear::Destroyed => destroyed,
+                WaveClear::Survive(seconds) => destroyed || wave_director.elapsed >= seconds,
+            };
+            if cleared {
+                let wave = wave_director.wave;
+                info!("wave {wave} cleared");
+                wave_director.clear();
+                cleared_events.send(WaveCleared { wave });
+            }
+        }
+    }
+}
@@ -0,0 +1,148 @@
+use crate::component::{collider::Layers, hitbox::WorldShape};
+use bevy::{prelude::*, utils::HashMap};
+
+/// Resource - Uniform grid of the colliders, rebuilt every tick
+///
+/// Each collider is stored in every cell its bounds touch, so only colliders sharing
+/// a cell have their shapes tested against each other.
+#[derive(Resource, Debug)]
+pub struct SpatialHash {
+    pub cell_size: f32,
+    pub cells: HashMap<IVec2, Vec<usize>>,
+    pub entries: Vec<SpatialEntry>,
+}
+
+#[derive(Debug, Clone)]
+pub struct SpatialEntry {
+    pub entity: Entity,
+    pub shape: WorldShape,
+    /// bounds of the shape
+    pub min: Vec2,
+    pub max: Vec2,
+    pub layer: Layers,
+    pub mask: Layers,
+}
+
+impl SpatialHash {
+    pub fn new(cell_size: f32) -> Self {
+        Self {
+            cell_size,
+            cells: HashMap::default(),
+            entries: Vec::new(),
+        }
+    }
+
+    /// Empty the grid, keeping the cell allocations for the next tick
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        for cell in self.cells.values_mut() {
+            cell.clear();
+        }
+    }
+
+    pub fn insert(&mut self, entry: SpatialEntry) {
+        let index = self.entries.len();
+        let (min, max) = (self.cell(entry.min), self.cell(entry.max));
+        for y in min.y..=max.y {
+            for x in min.x..=max.x {
+                self.cells.entry(IVec2::new(x, y)).or_default().push(index);
+            }
+        }
+        self.entries.push(entry);
+    }
+
+    fn cell(&self, position: Vec2) -> IVec2 {
+        (position / self.cell_size).floor().as_ivec2()
+    }
+
+    /// Entries overlapping `entry` on a layer of its mask, each reported once
+    pub fn overlaps<'a>(
+        &'a self,
+        entry: &'a SpatialEntry,
+    ) -> impl Iterator<Item = &'a SpatialEntry> {
+        let (min, max) = (self.cell(entry.min), self.cell(entry.max));
+        (min.y..=max.y)
+            .flat_map(move |y| (min.x..=max.x).map(move |x| IVec2::new(x, y)))
+            .flat_map(move |cell| {
+                self.cells
+                    .get(&cell)
+                    .into_iter()
+                    .flatten()
+                    .map(move |index| (cell, &self.entries[*index]))
+            })
+            .filter(move |(cell, other)| {
+                other.entity != entry.entity
+                    && entry.mask.intersects(other.layer)
+                    && entry.min.cmplt(other.max).all()
+                    && other.min.cmplt(entry.max).all()
+                    // a pair sharing several cells only counts in the first one
+                    && *cell == self.cell(entry.min.max(other.min))
+                    && entry.shape.intersects(&other.shape)
+            })
+            .map(|(_, other)| other)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Square collider of side `size` around `center`
+    fn entry(index: u32, center: Vec2, size: f32, layer: Layers, mask: Layers) -> SpatialEntry {
+        let half = size / 2.;
+        let shape = WorldShape {
+            points: [(-half, -half), (half, -half), (half, half), (-half, half)]
+                .map(|(x, y)| center + Vec2::new(x, y))
+                .to_vec(),
+            radius: 0.,
+        };
+        let (min, max) = shape.bounds();
+        SpatialEntry {
+            entity: Entity::from_raw(index),
+            shape,
+            min,
+            max,
+            layer,
+            mask,
+        }
+    }
+
+    fn overlapping(hash: &SpatialHash, entry: &SpatialEntry) -> Vec<Entity> {
+        hash.overlaps(entry).map(|other| other.entity).collect()
+    }
+
+    #[test]
+    fn overlap_spanning_cells_is_reported_once() {
+        let mut hash = SpatialHash::new(10.);
+        // both squares cover the four cells around the origin
+        let player = entry(0, Vec2::ZERO, 8., Layers::PLAYER, Layers::ENEMY);
+        let enemy = entry(1, Vec2::new(1., 1.), 8., Layers::ENEMY, Layers::PLAYER);
+        hash.insert(player.clone());
+        hash.insert(enemy.clone());
+        assert_eq!(overlapping(&hash, &player), vec![enemy.entity]);
+        assert_eq!(overlapping(&hash, &enemy), vec![player.entity]);
+    }
+
+    #[test]
+    fn overlap_needs_the_mask_and_touching_shapes() {
+        let mut hash = SpatialHash::new(10.);
+        let player = entry(0, Vec2::ZERO, 8., Layers::PLAYER, Layers::ENEMY);
+        let pickup = entry(1, Vec2::ZERO, 8., Layers::PICKUP, Layers::NONE);
+        let enemy = entry(2, Vec2::new(9., 0.), 8., Layers::ENEMY, Layers::PLAYER);
+        for entry in [&player, &pickup, &enemy] {
+            hash.insert(entry.clone());
+        }
+        assert!(overlapping(&hash, &player).is_empty());
+        assert!(overlapping(&hash, &pickup).is_empty());
+    }
+
+    #[test]
+    fn clear_empties_the_grid() {
+        let mut hash = SpatialHash::new(10.);
+        let player = entry(0, Vec2::ZERO, 8., Layers::PLAYER, Layers::ENEMY);
+        hash.insert(entry(1, Vec2::ZERO, 8., Layers::ENEMY, Layers::PLAYER));
+        hash.clear();
+        assert!(hash.entries.is_empty());
+        assert!(overlapping(&hash, &player).is_empty());
+    }
+}
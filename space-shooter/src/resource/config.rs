@@ -0,0 +1,83 @@
+use crate::PLAYER_INVULNERABILITY;
+use bevy::prelude::*;
+use std::path::PathBuf;
+
+/// Resource - Game Config (parsed from the command line)
+#[derive(Resource, Debug, Clone)]
+pub struct GameConfig {
+    /// run without window, rendering or audio
+    pub headless: bool,
+    /// exit after this many frames (headless only)
+    pub frames: Option<u64>,
+    /// seed for the game RNG, random when not given
+    pub seed: Option<u64>,
+    /// record the keyboard input into this replay file
+    pub record: Option<PathBuf>,
+    /// play this replay file back instead of the keyboard
+    pub replay: Option<PathBuf>,
+    /// gameplay ticks per second (FixedUpdate)
+    pub tick_rate: f64,
+    /// seconds a respawned ship takes no hits
+    pub invulnerability: f32,
+    /// player name for the high score table
+    pub name: String,
+}
+
+impl Default for GameConfig {
+    fn default() -> Self {
+        Self {
+            headless: false,
+            frames: None,
+            seed: None,
+            record: None,
+            replay: None,
+            tick_rate: 60.,
+            invulnerability: PLAYER_INVULNERABILITY,
+            name: std::env::var("USER")
+                .or_else(|_| std::env::var("USERNAME"))
+                .unwrap_or_else(|_| "player".into()),
+        }
+    }
+}
+
+impl GameConfig {
+    pub fn from_args() -> Result<Self, String> {
+        let mut config = Self::default();
+        let mut args = std::env::args().skip(1);
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--headless" => config.headless = true,
+                "--frames" => config.frames = Some(parse_value(&arg, args.next())?),
+                "--seed" => config.seed = Some(parse_value(&arg, args.next())?),
+                "--record" => config.record = Some(parse_value(&arg, args.next())?),
+                "--replay" => config.replay = Some(parse_value(&arg, args.next())?),
+                "--tick-rate" => config.tick_rate = parse_value(&arg, args.next())?,
+                "--invulnerability" => config.invulnerability = parse_value(&arg, args.next())?,
+                "--name" => config.name = parse_value(&arg, args.next())?,
+                _ => return Err(format!("unknown argument `{arg}`")),
+            }
+        }
+        if !(config.tick_rate.is_finite() && config.tick_rate > 0.) {
+            return Err(format!("invalid tick rate `{}`", config.tick_rate));
+        }
+        if !(config.invulnerability.is_finite() && config.invulnerability >= 0.) {
+            return Err(format!(
+                "invalid invulnerability `{}`",
+                config.invulnerability
+            ));
+        }
+        Ok(config)
+    }
+}
+
+fn parse_value<T: std::str::FromStr>(flag: &str, value: Option<String>) -> Result<T, String> {
+    let value = value.ok_or_else(|| format!("missing value for `{flag}`"))?;
+    value
+        .parse()
+        .map_err(|_| format!("invalid value `{value}` for `{flag}`"))
+}
+
+/// Run condition - the game is presented in a window (rendering + audio)
+pub fn windowed(config: Res<GameConfig>) -> bool {
+    !config.headless
+}
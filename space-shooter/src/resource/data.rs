@@ -0,0 +1,33 @@
+use crate::asset::{
+    boss::BossLibrary, enemy::EnemyArchetypes, meteor::MeteorField, path::PathLibrary,
+    pickup::LootTable, sheet::SpriteSheet, wave::WaveScript, weapon::WeaponLibrary,
+};
+use bevy::prelude::*;
+
+/// Resource - Handles of the data assets the game needs before it can start
+#[derive(Resource, Default, Debug, Clone)]
+pub struct GameData {
+    pub enemies: Handle<EnemyArchetypes>,
+    pub waves: Handle<WaveScript>,
+    pub paths: Handle<PathLibrary>,
+    pub bosses: Handle<BossLibrary>,
+    pub pickups: Handle<LootTable>,
+    pub weapons: Handle<WeaponLibrary>,
+    pub meteors: Handle<MeteorField>,
+    pub sheet: Handle<SpriteSheet>,
+}
+
+impl GameData {
+    pub fn handles(&self) -> Vec<UntypedHandle> {
+        vec![
+            self.enemies.clone().untyped(),
+            self.waves.clone().untyped(),
+            self.paths.clone().untyped(),
+            self.bosses.clone().untyped(),
+            self.pickups.clone().untyped(),
+            self.weapons.clone().untyped(),
+            self.meteors.clone().untyped(),
+            self.sheet.clone().untyped(),
+        ]
+    }
+}
@@ -1,23 +1,77 @@
-use bevy::prelude::*;
+use crate::{
+    asset::{hitbox::HitboxShape, weapon::WeaponKind},
+    component::{explosion::ExplosionKind, hitbox::Hitbox},
+    COMBO_MAX, COMBO_WINDOW, PLAYER_LIVES,
+};
+use bevy::{prelude::*, utils::HashMap};
 
+/// Resource - Textures of the game, every sprite but the background is an index of `sheet`
 #[derive(Resource, Default, Debug, Clone)]
 pub struct GameTextures {
     pub background: Handle<Image>,
-    pub player: Handle<Image>,
-    pub player_bucket: Vec<Handle<Image>>,
-    pub player_laser: Handle<Image>,
-    pub player_explosion: Handle<Image>,
-    pub enemy: Handle<Image>,
-    pub enemy_bucket: Vec<Handle<Image>>,
-    pub enemy_laser: Handle<Image>,
-    pub enemy_explosion: Handle<Image>,
-    pub explosion_bucket: Vec<Handle<Image>>,
+    /// atlas of Spritesheet/sheet.xml, filled in once loaded
+    pub sheet: Handle<TextureAtlas>,
+    pub player: usize,
+    /// engine flame frames
+    pub player_engine: Vec<usize>,
+    /// damage overlays, from lightly to badly damaged
+    pub player_damage: Vec<usize>,
+    /// shield overlays, from one hit left to fully charged
+    pub player_shield: Vec<usize>,
+    /// enemy sprites by archetype name
+    pub enemies: HashMap<String, usize>,
+    pub enemy_laser: usize,
+    pub enemy_orb: usize,
+    /// boss sprites by boss name
+    pub bosses: HashMap<String, usize>,
+    pub boss_beam: usize,
+    pub boss_beam_muzzle: usize,
+    /// explosion frames by kind
+    pub explosions: HashMap<ExplosionKind, Vec<usize>>,
+    /// power-up sprites by pickup name
+    pub pickups: HashMap<String, usize>,
+    /// player projectile sprites by weapon
+    pub weapons: HashMap<WeaponKind, usize>,
+    /// meteor sprites by name
+    pub meteors: HashMap<String, usize>,
+    /// hulls of the `Mask` hitboxes by sprite and threshold bits, computed while loading
+    pub hulls: HashMap<(usize, u32), Vec<(f32, f32)>>,
+}
+
+impl GameTextures {
+    /// Hitbox of the sprite `index`, a mask is replaced by its hull
+    pub fn hitbox(&self, shape: &HitboxShape, index: usize) -> Hitbox {
+        match shape {
+            HitboxShape::Mask { threshold } => Hitbox(
+                self.hulls
+                    .get(&(index, threshold.to_bits()))
+                    .map_or(HitboxShape::Rect, |points| HitboxShape::Polygon {
+                        points: points.clone(),
+                    }),
+            ),
+            shape => Hitbox(shape.clone()),
+        }
+    }
+}
+
+#[derive(Resource, Default, Debug, Clone)]
+pub struct HudAssets {
+    pub font: Handle<Font>,
+    pub life: Handle<Image>,
+    pub numerals: Vec<Handle<Image>>,
+    pub numeral_x: Handle<Image>,
 }
 
 #[derive(Resource, Debug, Clone)]
 pub struct PlayerState {
     pub alive: bool,
     pub last_shot: Option<f64>,
+    pub score: u32,
+    /// ships left, including the one in play
+    pub lives: u32,
+    /// score multiplier, grows with kills chained within `COMBO_WINDOW`
+    pub combo: u32,
+    pub last_kill: Option<f64>,
 }
 
 impl Default for PlayerState {
@@ -25,6 +79,10 @@ impl Default for PlayerState {
         Self {
             alive: false,
             last_shot: None,
+            score: 0,
+            lives: PLAYER_LIVES,
+            combo: 1,
+            last_kill: None,
         }
     }
 }
@@ -38,5 +96,29 @@ impl PlayerState {
     pub fn shot(&mut self, instant: f64) {
         self.alive = false;
         self.last_shot = Some(instant);
+        self.lives = self.lives.saturating_sub(1);
+        self.combo = 1;
+    }
+
+    pub fn game_over(&self) -> bool {
+        self.lives == 0
+    }
+
+    /// Award an enemy kill, returns the points scored with the combo multiplier applied
+    pub fn kill(&mut self, points: u32, instant: f64) -> u32 {
+        self.combo = if self.combo_active(instant) {
+            (self.combo + 1).min(COMBO_MAX)
+        } else {
+            1
+        };
+        self.last_kill = Some(instant);
+        let points = points * self.combo;
+        self.score += points;
+        points
+    }
+
+    pub fn combo_active(&self, instant: f64) -> bool {
+        self.last_kill
+            .is_some_and(|last_kill| instant < last_kill + COMBO_WINDOW)
     }
 }
@@ -0,0 +1,136 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+pub const HIGHSCORE_VERSION: u32 = 1;
+pub const HIGHSCORE_MAX: usize = 10;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct HighScoreEntry {
+    pub name: String,
+    pub score: u32,
+    /// unix timestamp in seconds
+    pub date: u64,
+}
+
+/// High score file - top `HIGHSCORE_MAX` runs, best first
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct HighScoreFile {
+    version: u32,
+    entries: Vec<HighScoreEntry>,
+}
+
+/// Resource - High score table, persisted in the platform data dir
+#[derive(Resource, Debug, Clone)]
+pub struct HighScores {
+    pub path: Option<PathBuf>,
+    pub entries: Vec<HighScoreEntry>,
+    /// rank of the last recorded run, if it made it into the table
+    pub last_rank: Option<usize>,
+}
+
+impl HighScores {
+    /// `<data dir>/space-shooter/highscores.ron`, `None` on platforms without a data dir
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::data_dir().map(|dir| dir.join(crate::APP_NAME).join("highscores.ron"))
+    }
+
+    /// Load the table, a missing, corrupt or outdated file gives an empty one
+    pub fn load(path: Option<PathBuf>) -> Self {
+        let entries = match path.as_deref().map(read_entries) {
+            Some(Ok(entries)) => entries,
+            Some(Err(err)) => {
+                warn!("{err}, starting a new high score table");
+                Vec::new()
+            }
+            None => Vec::new(),
+        };
+        Self {
+            path,
+            entries,
+            last_rank: None,
+        }
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let file = HighScoreFile {
+            version: HIGHSCORE_VERSION,
+            entries: self.entries.clone(),
+        };
+        let text = ron::ser::to_string_pretty(&file, Default::default())
+            .map_err(|err| format!("cannot serialize high scores: {err}"))?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)
+                .map_err(|err| format!("cannot create {}: {err}", dir.display()))?;
+        }
+        // write aside and rename, so a crash never leaves a half written table
+        let tmp = path.with_extension("ron.tmp");
+        fs::write(&tmp, text).map_err(|err| format!("cannot write {}: {err}", tmp.display()))?;
+        fs::rename(&tmp, path).map_err(|err| format!("cannot write {}: {err}", path.display()))
+    }
+
+    /// Insert a run, returns its rank if it made it into the table
+    pub fn record(&mut self, name: &str, score: u32) -> Option<usize> {
+        let date = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_secs());
+        // ties keep the older run first
+        let rank = self.entries.partition_point(|entry| entry.score >= score);
+        self.last_rank = (rank < HIGHSCORE_MAX).then(|| {
+            self.entries.insert(
+                rank,
+                HighScoreEntry {
+                    name: name.to_owned(),
+                    score,
+                    date,
+                },
+            );
+            self.entries.truncate(HIGHSCORE_MAX);
+            rank
+        });
+        self.last_rank
+    }
+}
+
+fn read_entries(path: &Path) -> Result<Vec<HighScoreEntry>, String> {
+    let text = match fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(format!("cannot read {}: {err}", path.display())),
+    };
+    let file: HighScoreFile =
+        ron::from_str(&text).map_err(|err| format!("corrupt {}: {err}", path.display()))?;
+    if file.version != HIGHSCORE_VERSION {
+        return Err(format!(
+            "unsupported high score version {} in {}",
+            file.version,
+            path.display()
+        ));
+    }
+    let mut entries = file.entries;
+    entries.sort_by_key(|entry| std::cmp::Reverse(entry.score));
+    entries.truncate(HIGHSCORE_MAX);
+    Ok(entries)
+}
+
+/// Format a unix timestamp as `YYYY-MM-DD` (UTC)
+pub fn format_date(timestamp: u64) -> String {
+    // days to civil date, see http://howardhinnant.github.io/date_algorithms.html
+    let z = (timestamp / 86_400) as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!("{year:04}-{month:02}-{day:02}")
+}
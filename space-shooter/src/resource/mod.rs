@@ -1 +1,11 @@
+pub mod broadphase;
+pub mod config;
+pub mod data;
 pub mod global;
+pub mod highscore;
+pub mod pickup;
+pub mod playfield;
+pub mod pool;
+pub mod replay;
+pub mod rng;
+pub mod wave;
@@ -0,0 +1,97 @@
+use crate::asset::pickup::{PickupDef, PickupEffect, Stacking};
+use bevy::prelude::*;
+
+/// Resource - Timed power-up effects the player has picked up, in pickup order
+#[derive(Resource, Default, Debug, Clone)]
+pub struct PowerUps {
+    pub active: Vec<ActivePowerUp>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ActivePowerUp {
+    /// pickup name, the same pickup stacks by its stacking rule
+    pub name: String,
+    pub effect: PickupEffect,
+    pub stacks: u32,
+    /// seconds left
+    pub remaining: f32,
+    /// seconds the last pickup lasts, for the HUD timer
+    pub duration: f32,
+}
+
+impl PowerUps {
+    /// Activate the effect of `pickup`, or stack it by its rule when already active
+    ///
+    /// Instant effects are not tracked, they are applied by the caller.
+    pub fn grant(&mut self, pickup: &PickupDef) {
+        if pickup.effect.instant() {
+            return;
+        }
+        let Some(active) = self
+            .active
+            .iter_mut()
+            .find(|active| active.name == pickup.name)
+        else {
+            self.active.push(ActivePowerUp {
+                name: pickup.name.clone(),
+                effect: pickup.effect,
+                stacks: 1,
+                remaining: pickup.duration,
+                duration: pickup.duration,
+            });
+            return;
+        };
+        match pickup.stacking {
+            Stacking::Refresh => {
+                active.remaining = pickup.duration;
+            }
+            Stacking::Extend => {
+                active.remaining += pickup.duration;
+                active.duration = active.remaining;
+            }
+            Stacking::Stack { max } => {
+                active.stacks = (active.stacks + 1).min(max);
+                active.remaining = pickup.duration;
+            }
+            Stacking::Ignore => {}
+        }
+    }
+
+    /// Run the timers down, returns the effects that ran out
+    pub fn tick(&mut self, delta: f32) -> Vec<ActivePowerUp> {
+        let mut expired = Vec::new();
+        self.active.retain_mut(|active| {
+            active.remaining -= delta;
+            if active.remaining > 0. {
+                return true;
+            }
+            expired.push(active.clone());
+            false
+        });
+        expired
+    }
+
+    fn stacks(&self, matches: impl Fn(&PickupEffect) -> bool) -> u32 {
+        self.active
+            .iter()
+            .filter(|active| matches(&active.effect))
+            .map(|active| active.stacks)
+            .sum()
+    }
+
+    /// Weapon level, 1 without any weapon upgrade
+    pub fn weapon_level(&self) -> u32 {
+        1 + self.stacks(|effect| *effect == PickupEffect::Weapon)
+    }
+
+    /// Factor applied to kill points
+    pub fn score_multiplier(&self) -> u32 {
+        self.active
+            .iter()
+            .filter_map(|active| match active.effect {
+                PickupEffect::ScoreMultiplier { multiplier } => Some(multiplier * active.stacks),
+                _ => None,
+            })
+            .product()
+    }
+}
@@ -0,0 +1,23 @@
+use bevy::prelude::*;
+
+/// Resource - Playfield bounds, centered on the origin
+///
+/// Gameplay systems read this instead of the `PrimaryWindow`, so the game also runs headless.
+#[derive(Resource, Debug, Clone, Copy)]
+pub struct PlayField {
+    pub w: f32,
+    pub h: f32,
+}
+
+impl From<(f32, f32)> for PlayField {
+    fn from((w, h): (f32, f32)) -> Self {
+        Self { w, h }
+    }
+}
+
+impl PlayField {
+    /// true if `translation` lies inside the playfield grown by `margin` on every side
+    pub fn contains(&self, translation: Vec3, margin: f32) -> bool {
+        translation.x.abs() <= self.w / 2. + margin && translation.y.abs() <= self.h / 2. + margin
+    }
+}
@@ -0,0 +1,88 @@
+use crate::component::{
+    animation::Animation, collider::Collider, damage::Damage, emitter::Homing, enemy::FromEnemy,
+    explosion::Explosion, hitbox::Hitbox, laser::Laser, movement::Movable, pickup::Pickup,
+    player::FromPlayer, pool::Pooled, sprite::SpriteSize, state::InGame, velocity::Velocity,
+    weapon::Piercing,
+};
+use bevy::{
+    ecs::system::{Command, EntityCommands},
+    hierarchy::despawn_with_children_recursive,
+    prelude::*,
+};
+
+/// Resource - Hidden sprite entities, reused for projectiles and effects
+///
+/// Counts how many spawns were served from the pool (`hits`) and how many
+/// needed a new entity (`misses`).
+#[derive(Resource, Default, Debug)]
+pub struct SpritePool {
+    pub free: Vec<Entity>,
+    pub hits: u64,
+    pub misses: u64,
+}
+
+impl SpritePool {
+    /// Entity to insert a new `SpriteBundle` and its components into
+    pub fn acquire<'w, 's, 'a>(
+        &mut self,
+        commands: &'a mut Commands<'w, 's>,
+    ) -> EntityCommands<'w, 's, 'a> {
+        let pooled = Pooled { active: true };
+        match self.free.pop() {
+            Some(entity) => {
+                self.hits += 1;
+                let mut entity = commands.entity(entity);
+                entity.insert(pooled);
+                entity
+            }
+            None => {
+                self.misses += 1;
+                commands.spawn(pooled)
+            }
+        }
+    }
+}
+
+/// Command - Hide a pooled entity and hand it back to the `SpritePool`,
+/// any other entity is despawned with its children
+pub struct Release(pub Entity);
+
+impl Command for Release {
+    fn apply(self, world: &mut World) {
+        let Some(mut entity) = world.get_entity_mut(self.0) else {
+            return;
+        };
+        match entity.get::<Pooled>() {
+            Some(pooled) if pooled.active => {}
+            // released twice in the same tick
+            Some(_) => return,
+            None => {
+                despawn_with_children_recursive(world, self.0);
+                return;
+            }
+        }
+        // everything a projectile or effect is spawned with, on top of its SpriteSheetBundle
+        entity
+            .remove::<(
+                Laser,
+                FromPlayer,
+                FromEnemy,
+                Damage,
+                Homing,
+                Piercing,
+                Movable,
+                Velocity,
+            )>()
+            .remove::<(
+                Collider,
+                Hitbox,
+                Explosion,
+                Animation,
+                Pickup,
+                SpriteSize,
+                InGame,
+            )>()
+            .insert((Pooled { active: false }, Visibility::Hidden));
+        world.resource_mut::<SpritePool>().free.push(self.0);
+    }
+}
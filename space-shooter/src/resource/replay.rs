@@ -0,0 +1,187 @@
+use crate::PLAYER_INVULNERABILITY;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+pub const REPLAY_VERSION: u32 = 3;
+
+/// Keys captured per gameplay tick, bit `i` of a tick mask is `REPLAY_KEYS[i]`
+pub const REPLAY_KEYS: [KeyCode; 6] = [
+    KeyCode::Left,
+    KeyCode::Right,
+    KeyCode::Up,
+    KeyCode::Down,
+    KeyCode::Space,
+    KeyCode::X,
+];
+
+/// Replay file - RNG seed, tick rate, respawn invulnerability and the pressed keys of every
+/// gameplay tick
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct Replay {
+    pub version: u32,
+    pub seed: u64,
+    pub tick_rate: f64,
+    #[serde(default = "default_invulnerability")]
+    pub invulnerability: f32,
+    pub ticks: Vec<u8>,
+    /// state at the end of the recording, checked again after playback
+    pub outcome: Option<ReplayOutcome>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ReplayOutcome {
+    pub entities: u32,
+    #[serde(default)]
+    pub score: u32,
+}
+
+fn default_invulnerability() -> f32 {
+    PLAYER_INVULNERABILITY
+}
+
+impl Replay {
+    pub fn new(seed: u64, tick_rate: f64, invulnerability: f32) -> Self {
+        Self {
+            version: REPLAY_VERSION,
+            seed,
+            tick_rate,
+            invulnerability,
+            ..Default::default()
+        }
+    }
+
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let text = fs::read_to_string(path)
+            .map_err(|err| format!("cannot read replay {}: {err}", path.display()))?;
+        let replay: Self = ron::from_str(&text)
+            .map_err(|err| format!("cannot parse replay {}: {err}", path.display()))?;
+        if replay.version != REPLAY_VERSION {
+            return Err(format!(
+                "unsupported replay version {} (expected {REPLAY_VERSION})",
+                replay.version
+            ));
+        }
+        Ok(replay)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let pretty = ron::ser::PrettyConfig::new().compact_arrays(true);
+        let text = ron::ser::to_string_pretty(self, pretty)
+            .map_err(|err| format!("cannot serialize replay: {err}"))?;
+        fs::write(path, text)
+            .map_err(|err| format!("cannot write replay {}: {err}", path.display()))
+    }
+}
+
+/// Pack the replay keys currently pressed into a tick mask
+pub fn tick_mask(kb: &Input<KeyCode>) -> u8 {
+    REPLAY_KEYS
+        .iter()
+        .enumerate()
+        .filter(|(_, key)| kb.pressed(**key))
+        .fold(0, |mask, (i, _)| mask | 1 << i)
+}
+
+/// Resource - Replay being recorded, saved to `path` on exit
+#[derive(Resource, Debug)]
+pub struct ReplayRecorder {
+    pub path: PathBuf,
+    pub replay: Replay,
+}
+
+impl ReplayRecorder {
+    pub fn new(path: PathBuf, seed: u64, tick_rate: f64, invulnerability: f32) -> Self {
+        Self {
+            path,
+            replay: Replay::new(seed, tick_rate, invulnerability),
+        }
+    }
+}
+
+/// Resource - Replay being played back instead of the keyboard
+#[derive(Resource, Debug)]
+pub struct ReplayPlayback {
+    pub replay: Replay,
+    pub cursor: usize,
+    pub previous: u8,
+}
+
+impl ReplayPlayback {
+    pub fn new(replay: Replay) -> Self {
+        Self {
+            replay,
+            cursor: 0,
+            previous: 0,
+        }
+    }
+
+    pub fn finished(&self) -> bool {
+        self.cursor >= self.replay.ticks.len()
+    }
+
+    /// Next tick mask, `None` once every tick is played
+    pub fn next_tick(&mut self) -> Option<u8> {
+        let mask = *self.replay.ticks.get(self.cursor)?;
+        self.cursor += 1;
+        Some(mask)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("{name}-{}.ron", std::process::id()))
+    }
+
+    #[test]
+    fn save_load_round_trip() {
+        let mut replay = Replay::new(42, 60., 1.5);
+        replay.ticks = vec![0, 1, 0b1_0000, 0b1_1111];
+        replay.outcome = Some(ReplayOutcome {
+            entities: 12,
+            score: 3400,
+        });
+        let path = temp_path("replay-round-trip");
+        replay.save(&path).unwrap();
+        let loaded = Replay::load(&path);
+        fs::remove_file(&path).unwrap();
+        let loaded = loaded.unwrap();
+        assert_eq!(loaded.version, REPLAY_VERSION);
+        assert_eq!(loaded.seed, 42);
+        assert_eq!(loaded.tick_rate, 60.);
+        assert_eq!(loaded.invulnerability, 1.5);
+        assert_eq!(loaded.ticks, replay.ticks);
+        assert_eq!(loaded.outcome, replay.outcome);
+    }
+
+    #[test]
+    fn load_rejects_other_versions() {
+        let replay = Replay {
+            version: REPLAY_VERSION + 1,
+            ..Replay::new(1, 60., 1.)
+        };
+        let path = temp_path("replay-version");
+        replay.save(&path).unwrap();
+        let loaded = Replay::load(&path);
+        fs::remove_file(&path).unwrap();
+        assert!(loaded.unwrap_err().contains("unsupported replay version"));
+    }
+
+    #[test]
+    fn playback_runs_out() {
+        let mut playback = ReplayPlayback::new(Replay {
+            ticks: vec![3, 5],
+            ..Default::default()
+        });
+        assert_eq!(playback.next_tick(), Some(3));
+        assert_eq!(playback.next_tick(), Some(5));
+        assert!(playback.finished());
+        assert_eq!(playback.next_tick(), None);
+    }
+}
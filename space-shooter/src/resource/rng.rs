@@ -0,0 +1,61 @@
+use bevy::prelude::*;
+use rand::{rngs::StdRng, SeedableRng};
+
+/// Resource - Game RNG shared by every gameplay system
+///
+/// The same seed replays the same enemy formations and firing pattern.
+#[derive(Resource, Deref, DerefMut)]
+pub struct GameRng {
+    seed: u64,
+    #[deref]
+    rng: StdRng,
+}
+
+impl GameRng {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Start over from the seed, so every game with the same seed plays the same
+    pub fn reseed(&mut self) {
+        self.rng = StdRng::seed_from_u64(self.seed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::Rng;
+
+    fn draws(rng: &mut GameRng) -> Vec<u32> {
+        (0..8).map(|_| rng.gen()).collect()
+    }
+
+    #[test]
+    fn same_seed_same_draws() {
+        let (mut a, mut b) = (GameRng::new(42), GameRng::new(42));
+        assert_eq!(draws(&mut a), draws(&mut b));
+        assert_eq!(a.seed(), 42);
+    }
+
+    #[test]
+    fn other_seed_other_draws() {
+        assert_ne!(draws(&mut GameRng::new(42)), draws(&mut GameRng::new(43)));
+    }
+
+    #[test]
+    fn reseed_starts_over() {
+        let mut rng = GameRng::new(7);
+        let first = draws(&mut rng);
+        assert_ne!(draws(&mut rng), first);
+        rng.reseed();
+        assert_eq!(draws(&mut rng), first);
+    }
+}
@@ -0,0 +1,85 @@
+use crate::asset::wave::WaveDef;
+use bevy::prelude::*;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum WavePhase {
+    /// waiting before the next wave starts
+    #[default]
+    Intermission,
+    Active,
+}
+
+/// Resource - Wave Director, tracks the current wave and paces its spawns
+#[derive(Resource, Default, Debug, Clone)]
+pub struct WaveDirector {
+    /// current wave, 0 before the first one
+    pub wave: u32,
+    pub phase: WavePhase,
+    pub def: Option<WaveDef>,
+    /// seconds since the phase began
+    pub elapsed: f32,
+    pub spawned: u32,
+    /// seconds until the next spawn is allowed
+    pub next_spawn: f32,
+    /// seconds until the next meteor
+    pub next_meteor: f32,
+}
+
+impl WaveDirector {
+    pub fn start(&mut self, wave: u32, def: WaveDef) {
+        *self = Self {
+            wave,
+            phase: WavePhase::Active,
+            // the first meteor comes one cadence in, not on the first tick
+            next_meteor: def.meteors,
+            def: Some(def),
+            ..Default::default()
+        };
+    }
+
+    pub fn clear(&mut self) {
+        self.phase = WavePhase::Intermission;
+        self.elapsed = 0.;
+    }
+
+    /// Formations the current wave spawns in total
+    pub fn total(&self) -> u32 {
+        self.def.as_ref().map_or(0, |def| def.formations)
+    }
+
+    pub fn all_spawned(&self) -> bool {
+        self.spawned >= self.total()
+    }
+
+    /// true if the cadence and the alive cap allow the next formation spawn
+    pub fn can_spawn(&self, alive: u32) -> bool {
+        self.def.as_ref().is_some_and(|def| {
+            self.phase == WavePhase::Active
+                && !self.all_spawned()
+                && self.next_spawn <= 0.
+                && alive < def.max_alive
+        })
+    }
+
+    /// Claim the next formation spawn if the cadence and the alive cap allow it
+    pub fn take_spawn(&mut self, alive: u32) -> bool {
+        if !self.can_spawn(alive) {
+            return false;
+        }
+        self.spawned += 1;
+        self.next_spawn = self.def.as_ref().map_or(0., |def| def.cadence);
+        true
+    }
+
+    /// Claim the next meteor spawn if the current wave has meteors and its cadence allows it
+    pub fn take_meteor(&mut self) -> bool {
+        let Some(def) = &self.def else {
+            return false;
+        };
+        if self.phase != WavePhase::Active || def.meteors <= 0. || self.next_meteor > 0. {
+            return false;
+        }
+        self.next_meteor = def.meteors;
+        true
+    }
+}